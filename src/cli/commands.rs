@@ -0,0 +1,628 @@
+use clap::Subcommand;
+
+use std::path::Path;
+
+use tmt2_type_tracker::{damage::DamageObservation, export, files, files::ChartFormat, BattleMessage, Discovery, Effectiveness, TrackerError, TypeChart};
+
+use crate::cli::{deduce_matchups, Interruption, print_coverage, print_discoveries, print_history, print_type_chart_grid, print_type_effectiveness_map, resolve_merge_conflicts, run_tui, solve_damage, undo_changes};
+
+// Exit codes of the non-interactive commands, clap already uses 2 for invalid arguments
+pub const EXIT_FILE_ERROR: i32 = 1;
+pub const EXIT_INVALID_INPUT: i32 = 2;
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Add a new type to the type chart
+    AddType {
+        type_name: String,
+    },
+    /// Remove an existing type from the type chart
+    RemoveType {
+        type_name: String,
+    },
+    /// Rename a type, keeping all its matchups
+    RenameType {
+        type_name: String,
+        new_type_name: String,
+    },
+    /// Fold a type entered twice under another name into the other one, and remove it
+    Merge {
+        /// Type that is kept
+        type_name: String,
+        /// Type that is merged into the kept one and removed
+        merged_type_name: String,
+        /// Keep the matchups of the kept type when the types disagree, instead of asking
+        #[arg(long)]
+        keep_existing: bool,
+    },
+    /// Set the effectiveness of an attacking type against an opposing type (SE, Neutral, NVE, Immune or ?), add a ? if you are not sure (e.g. SE?)
+    Set {
+        attacking_type: String,
+        opposing_type: String,
+        #[arg(value_parser = parse_effectiveness)]
+        effectiveness: Effectiveness,
+        /// Where or how the matchup was learned, saved in the discovery log
+        #[arg(long)]
+        note: Option<String>,
+    },
+    /// See what a type is weak to/resists
+    Show {
+        type_name: String,
+        /// Also show the neutral matchups
+        #[arg(short, long)]
+        neutral: bool,
+    },
+    /// See what a Pokemon with all of these types is weak to/resists, the name of a species can be given instead of its types
+    Defend {
+        #[arg(required = true)]
+        type_names: Vec<String>,
+        /// Also show the neutral matchups
+        #[arg(short, long)]
+        neutral: bool,
+        /// Also show the matchups that depend on unknown effectivenesses
+        #[arg(short, long)]
+        unsure: bool,
+    },
+    /// See how well a moveset covers every type, and what walls it
+    Coverage {
+        #[arg(required = true)]
+        type_names: Vec<String>,
+        /// Also check the defenders with up to this many types
+        #[arg(short, long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=3))]
+        max_defending_types: u8,
+        /// Also show the neutral matchups
+        #[arg(short, long)]
+        neutral: bool,
+        /// Also show the matchups that depend on unknown effectivenesses
+        #[arg(short, long)]
+        unsure: bool,
+    },
+    /// See the entire type chart as a grid, attacking types are the rows
+    Chart {
+        /// Only show these types
+        #[arg(short, long, num_args = 1..)]
+        types: Vec<String>,
+        /// Don't color the cells
+        #[arg(long)]
+        no_color: bool,
+    },
+    /// Save the entire type chart as an image, or as the gTypeEffectiveness table of the Emerald decomp,
+    /// the format is picked from the extension (.svg, .png, or .c/.h for the table)
+    Export {
+        output: String,
+        /// Only show these types
+        #[arg(short, long, num_args = 1..)]
+        types: Vec<String>,
+    },
+    /// See or change the information saved with the type chart, an empty value removes it
+    Metadata {
+        key: Option<String>,
+        #[arg(requires = "key")]
+        value: Option<String>,
+    },
+    /// Convert a type chart from the old CSV format to the current chart file format
+    Migrate {
+        input: String,
+        /// Where to save the converted type chart, defaults to the same path with a .json extension
+        output: Option<String>,
+    },
+    /// Import a type chart from a CSV matrix with the attacking types as rows and the defending types as columns,
+    /// or from the gTypeEffectiveness table of an Emerald decomp source file (.c or .h)
+    Import {
+        input: String,
+        /// Where to save the imported type chart, defaults to the same path with a .json extension
+        output: Option<String>,
+        /// File defining the TYPE_ constants (like include/constants/pokemon.h), to keep the types that are neutral against everything
+        #[arg(long)]
+        types: Option<String>,
+    },
+    /// Import the species and their types from a species_info.h file of the Emerald decomp, adding the types the chart doesn't have
+    ImportSpecies {
+        input: String,
+        /// File defining the TYPE_ constants (like include/constants/pokemon.h), to add the new types in their order
+        #[arg(long)]
+        types: Option<String>,
+    },
+    /// Revert the last changes made to the type chart
+    Undo {
+        /// How many changes to revert
+        #[arg(default_value_t = 1)]
+        steps: usize,
+    },
+    /// Make the last undone changes again
+    Redo {
+        /// How many changes to make again
+        #[arg(default_value_t = 1)]
+        steps: usize,
+    },
+    /// See every change made to the type chart and when it was made
+    History,
+    /// See when and how matchups were learned, optionally only the ones of an attacking type or a pair of types
+    Learned {
+        attacking_type: Option<String>,
+        opposing_type: Option<String>,
+    },
+    /// Rebuild the matchups of the type chart from its discovery log, in a new chart file
+    Replay {
+        output: String,
+    },
+    /// Roll the type chart back to one of its backups, lists the backups when no number is given
+    Restore {
+        /// Number of the backup, 1 is the most recent one
+        backup: Option<usize>,
+    },
+    /// Browse and edit the type chart in a full screen grid, the changes are saved when leaving with q
+    Tui,
+    /// Record what the game said when a move hit a Pokemon, and fill the matchups it gives away
+    Observe {
+        attacking_type: String,
+        /// Every type of the Pokemon that was hit
+        #[arg(required = true)]
+        defending_types: Vec<String>,
+        /// What the game said (SE, Neutral for no message, NVE or Immune)
+        #[arg(short, long, value_parser = parse_battle_message)]
+        message: BattleMessage,
+    },
+    /// Fill the matchups the recorded battle messages leave a single effectiveness for, and report the ones that disagree
+    Deduce,
+    /// See the species and their types, or set the types of a species so that its name can be used instead of its types
+    Species {
+        species_name: Option<String>,
+        /// Types of the species, replacing the ones it had
+        #[arg(requires = "species_name")]
+        type_names: Vec<String>,
+        /// Remove the species from the species list
+        #[arg(long, requires = "species_name", conflicts_with = "type_names")]
+        remove: bool,
+    },
+    /// Work out the type multiplier of a move from the damage it dealt with Emerald's damage formula, and save the matchups it gives away
+    Damage {
+        attacking_type: String,
+        /// Every type of the Pokemon that was hit
+        #[arg(required = true)]
+        defending_types: Vec<String>,
+        /// Level of the attacker
+        #[arg(long)]
+        level: u32,
+        /// Attack or Special Attack of the attacker, depending on the move
+        #[arg(long)]
+        attack: u32,
+        /// Base power of the move
+        #[arg(long)]
+        power: u32,
+        /// Defense or Special Defense of the defender, depending on the move
+        #[arg(long)]
+        defense: u32,
+        /// Damage dealt, or the lowest and highest damage seen for the same attack (e.g. 28-33)
+        #[arg(long, value_parser = parse_damage_range)]
+        damage: (u32, u32),
+        /// HP the defender had left before the hit, when the hit made it faint
+        #[arg(long)]
+        hp: Option<u32>,
+        /// The move has one of the types of the attacker
+        #[arg(long)]
+        stab: bool,
+        /// The move was a critical hit
+        #[arg(long)]
+        critical: bool,
+        /// Save the matchups without asking
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+// Accepts the full effectiveness names as well as shortcuts like SE or NVE,
+// a ? after the effectiveness marks it as suspected
+pub fn parse_effectiveness(effectiveness: &str) -> Result<Effectiveness, TrackerError> {
+    return Effectiveness::parse_lenient(effectiveness);
+}
+
+// A single damage, or the lowest and highest damage seen written as 28-33
+pub fn parse_damage_range(damage: &str) -> Result<(u32, u32), String> {
+    let bounds = damage.split_once('-').unwrap_or((damage, damage));
+    match (bounds.0.trim().parse::<u32>(), bounds.1.trim().parse::<u32>()) {
+        (Ok(first_damage), Ok(second_damage)) => return Ok((first_damage.min(second_damage), first_damage.max(second_damage))),
+        _ => return Err(format!("{} is not a damage, write a number or a range like 28-33", damage.trim())),
+    }
+}
+
+fn parse_battle_message(message: &str) -> Result<BattleMessage, TrackerError> {
+    return message.parse();
+}
+
+fn error_to_exit_code(err: &TrackerError) -> i32 {
+    match err {
+        TrackerError::UnknownType(_) | TrackerError::AmbiguousType { .. } | TrackerError::MisspelledType { .. } | TrackerError::DuplicateType(_) | TrackerError::UnknownEffectiveness(_) | TrackerError::UnknownBattleMessage(_) | TrackerError::UnknownSpecies(_) | TrackerError::UnknownBackup(_) => EXIT_INVALID_INPUT,
+        _ => EXIT_FILE_ERROR,
+    }
+}
+
+// Loads the type chart and returns the path it should be saved to, legacy CSV files are migrated to a JSON chart file
+// next to them which is used from then on, unless `migrate` is false because the type chart is only read
+pub fn load_type_chart(filepath: &String, migrate: bool) -> Result<(TypeChart, String), i32> {
+    let format = match files::detect_format(filepath) {
+        Err(err) => {
+            eprintln!("Could not load the type chart from {}:", filepath);
+            eprintln!("{}", err);
+            return Err(error_to_exit_code(&err));
+        },
+        Ok(format) => format,
+    };
+    if format == ChartFormat::Json || !migrate {
+        match files::get_types_from_file(filepath) {
+            Err(err) => {
+                eprintln!("Could not load the type chart from {}:", filepath);
+                eprintln!("{}", err);
+                return Err(error_to_exit_code(&err));
+            },
+            Ok(type_chart) => return Ok((type_chart, filepath.clone())),
+        }
+    }
+    let new_filepath = files::migrated_filepath(filepath);
+    if Path::new(&new_filepath).exists() {
+        eprintln!("{} uses the old CSV format, but {} already exists", filepath, new_filepath);
+        eprintln!("Use -f {} to use the migrated type chart, or the migrate command to migrate it again", new_filepath);
+        return Err(EXIT_FILE_ERROR);
+    }
+    match files::migrate_legacy_file(filepath, &new_filepath) {
+        Err(err) => {
+            eprintln!("Could not migrate the type chart from {} to {}:", filepath, new_filepath);
+            eprintln!("{}", err);
+            return Err(error_to_exit_code(&err));
+        },
+        Ok(type_chart) => {
+            eprintln!("{} uses the old CSV format, it was migrated to {} which will be used from now on", filepath, new_filepath);
+            return Ok((type_chart, new_filepath));
+        },
+    }
+}
+
+fn save(type_chart: &TypeChart, filepath: &String) -> Result<(), i32> {
+    if let Err(err) = files::save_types_to_file(type_chart, filepath) {
+        eprintln!("Could not save the type chart to {}:", filepath);
+        eprintln!("{}", err);
+        return Err(EXIT_FILE_ERROR);
+    }
+    return Ok(());
+}
+
+fn export_type_chart(type_chart: &TypeChart, type_filter: &[String], output: &String) -> Result<(), TrackerError> {
+    let extension = std::path::Path::new(output).extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+    match extension.as_str() {
+        "svg" => return export::save_type_chart_svg(type_chart, type_filter, output),
+        #[cfg(feature = "png")]
+        "png" => return export::save_type_chart_png(type_chart, type_filter, output),
+        #[cfg(not(feature = "png"))]
+        "png" => return Err(TrackerError::Export("PNG support is disabled, build with --features png".to_string())),
+        "c" | "h" => return files::save_emerald_table(type_chart, type_filter, output),
+        _ => return Err(TrackerError::Export(format!("Unsupported format {:?}, use .svg or .png for an image, or .c for the decomp table", extension))),
+    }
+}
+
+fn import_type_chart(input: &String, output: Option<String>, types: Option<&String>) -> Result<(), i32> {
+    let output = output.unwrap_or_else(|| files::migrated_filepath(input));
+    // Never replace a type chart with an import
+    if Path::new(&output).exists() {
+        eprintln!("{} already exists, choose another file to import the type chart to", output);
+        return Err(EXIT_FILE_ERROR);
+    }
+    let extension = Path::new(input).extension().and_then(|extension| extension.to_str()).unwrap_or("").to_lowercase();
+    let imported = match extension.as_str() {
+        "c" | "h" => files::import_emerald_table(input, types),
+        _ => files::import_labelled_csv(input),
+    };
+    let (type_chart, report) = match imported {
+        Err(err) => {
+            eprintln!("Could not import the type chart from {}:", input);
+            eprintln!("{}", err);
+            return Err(error_to_exit_code(&err));
+        },
+        Ok(imported) => imported,
+    };
+    save(&type_chart, &output)?;
+    if report.repairs.is_empty() {
+        println!("Type chart imported from {} to {}", input, output);
+    } else {
+        println!("Type chart imported from {} to {} with {} repairs:", input, output, report.repairs.len());
+        for repair in &report.repairs {
+            println!("  {}", repair);
+        }
+    }
+    return Ok(());
+}
+
+fn import_species(type_chart: &mut TypeChart, input: &String, types: Option<&String>) -> Result<(), TrackerError> {
+    let previous_type_count = type_chart.get_type_list().len();
+    let previous_species = type_chart.get_species();
+    let report = files::import_emerald_species(type_chart, input, types)?;
+    let changed_species = type_chart.get_species().iter()
+        .filter(|(species_name, species_types)| previous_species.get(*species_name) != Some(*species_types))
+        .count();
+    println!("Imported {} species and {} new types from {}", changed_species, type_chart.get_type_list().len() - previous_type_count, input);
+    if !report.repairs.is_empty() {
+        println!("{} entries were ignored or repaired:", report.repairs.len());
+        for repair in &report.repairs {
+            println!("  {}", repair);
+        }
+    }
+    return Ok(());
+}
+
+fn replay_type_chart(type_chart: &TypeChart, filepath: &String, output: &String) -> Result<(), TrackerError> {
+    // Never replace a type chart with a replayed one
+    if Path::new(output).exists() {
+        return Err(TrackerError::Io(std::io::Error::new(std::io::ErrorKind::AlreadyExists, format!("{} already exists", output))));
+    }
+    let discoveries = files::read_discovery_log(filepath)?;
+    let (replayed_type_chart, skipped_discoveries) = files::replay_discoveries(&type_chart.get_type_list(), &discoveries);
+    files::save_types_to_file(&replayed_type_chart, output)?;
+    println!("Replayed {} discoveries of {} in {}", discoveries.len() - skipped_discoveries.len(), filepath, output);
+    if !skipped_discoveries.is_empty() {
+        println!("{} discoveries were skipped because their types aren't in the type chart anymore", skipped_discoveries.len());
+    }
+    return Ok(());
+}
+
+fn print_species(type_chart: &TypeChart) {
+    let species = type_chart.get_species();
+    if species.is_empty() {
+        println!("No species yet, add them with the species command");
+        return;
+    }
+    for (species_name, species_types) in species {
+        println!("{}: {}", species_name, species_types.join("/"));
+    }
+}
+
+fn print_backups(filepath: &String) -> Result<(), TrackerError> {
+    let backups = files::list_backups(filepath)?;
+    if backups.is_empty() {
+        println!("{} has no backups yet", filepath);
+        return Ok(());
+    }
+    println!("Backups of {}, from the most recent to the oldest:", filepath);
+    for (index, backup) in backups.iter().enumerate() {
+        let type_count = match files::get_types_from_file(&backup.filepath) {
+            Err(_) => "unreadable".to_string(),
+            Ok(type_chart) if type_chart.get_type_list().len() == 1 => "1 type".to_string(),
+            Ok(type_chart) => format!("{} types", type_chart.get_type_list().len()),
+        };
+        println!("{}) {} ({})", index + 1, backup.saved_at.format("%Y-%m-%d %H:%M:%S"), type_count);
+    }
+    return Ok(());
+}
+
+fn restore_backup(filepath: &String, number: usize) -> Result<TypeChart, TrackerError> {
+    let backups = files::list_backups(filepath)?;
+    match number.checked_sub(1).and_then(|index| backups.get(index)) {
+        None => return Err(TrackerError::UnknownBackup(number)),
+        Some(backup) => return files::read_backup(filepath, backup),
+    }
+}
+
+fn resolve_all(type_chart: &TypeChart, type_names: Vec<String>) -> Result<Vec<String>, TrackerError> {
+    return type_names.iter().map(|type_name| type_chart.resolve_type_name(type_name)).collect();
+}
+
+// Replaces the type names given by the user by the types they refer to, see TypeChart::resolve_type_name
+fn resolve_type_names(command: Command, type_chart: &TypeChart) -> Result<Command, TrackerError> {
+    let command = match command {
+        Command::RemoveType { type_name } => Command::RemoveType { type_name: type_chart.resolve_type_name(&type_name)? },
+        Command::RenameType { type_name, new_type_name } => Command::RenameType { type_name: type_chart.resolve_type_name(&type_name)?, new_type_name },
+        Command::Merge { type_name, merged_type_name, keep_existing } => Command::Merge {
+            type_name: type_chart.resolve_type_name(&type_name)?,
+            merged_type_name: type_chart.resolve_type_name(&merged_type_name)?,
+            keep_existing,
+        },
+        Command::Set { attacking_type, opposing_type, effectiveness, note } => Command::Set {
+            attacking_type: type_chart.resolve_type_name(&attacking_type)?,
+            opposing_type: type_chart.resolve_type_name(&opposing_type)?,
+            effectiveness,
+            note,
+        },
+        Command::Show { type_name, neutral } => Command::Show { type_name: type_chart.resolve_type_name(&type_name)?, neutral },
+        Command::Defend { type_names, neutral, unsure } => Command::Defend { type_names: type_chart.resolve_defender_types(&type_names)?, neutral, unsure },
+        Command::Coverage { type_names, max_defending_types, neutral, unsure } => {
+            Command::Coverage { type_names: resolve_all(type_chart, type_names)?, max_defending_types, neutral, unsure }
+        },
+        Command::Chart { types, no_color } => Command::Chart { types: resolve_all(type_chart, types)?, no_color },
+        Command::Export { output, types } => Command::Export { output, types: resolve_all(type_chart, types)? },
+        Command::Learned { attacking_type, opposing_type } => Command::Learned {
+            attacking_type: attacking_type.map(|attacking_type| type_chart.resolve_type_name(&attacking_type)).transpose()?,
+            opposing_type: opposing_type.map(|opposing_type| type_chart.resolve_type_name(&opposing_type)).transpose()?,
+        },
+        Command::Observe { attacking_type, defending_types, message } => Command::Observe {
+            attacking_type: type_chart.resolve_type_name(&attacking_type)?,
+            defending_types: type_chart.resolve_defender_types(&defending_types)?,
+            message,
+        },
+        Command::Damage { attacking_type, defending_types, level, attack, power, defense, damage, hp, stab, critical, yes } => Command::Damage {
+            attacking_type: type_chart.resolve_type_name(&attacking_type)?,
+            defending_types: type_chart.resolve_defender_types(&defending_types)?,
+            level, attack, power, defense, damage, hp, stab, critical, yes,
+        },
+        Command::Species { species_name, type_names, remove } => Command::Species { species_name, type_names: resolve_all(type_chart, type_names)?, remove },
+        command => command,
+    };
+    return Ok(command);
+}
+
+// Loads the type chart, runs a single command, and saves the type chart if the command changed it
+pub fn run_command(command: Command, filepath: &String, session: &Option<String>) -> Result<(), i32> {
+    // Migrating works on any file, not the one the other commands use
+    if let Command::Migrate { input, output } = command {
+        let output = output.unwrap_or_else(|| files::migrated_filepath(&input));
+        if let Err(err) = files::migrate_legacy_file(&input, &output) {
+            eprintln!("Could not migrate the type chart from {} to {}:", input, output);
+            eprintln!("{}", err);
+            return Err(error_to_exit_code(&err));
+        }
+        println!("Type chart migrated from {} to {}", input, output);
+        return Ok(());
+    }
+    if let Command::Import { input, output, types } = command {
+        return import_type_chart(&input, output, types.as_ref());
+    }
+    let edits_species = matches!(&command, Command::Species { type_names, remove, .. } if *remove || !type_names.is_empty());
+    let mut modifies_type_chart = edits_species || matches!(command, Command::AddType { .. } | Command::RemoveType { .. } | Command::RenameType { .. } | Command::Merge { .. } | Command::Set { .. } | Command::Metadata { value: Some(_), .. } | Command::Restore { backup: Some(_) } | Command::Undo { .. } | Command::Redo { .. } | Command::Observe { .. } | Command::Deduce | Command::Damage { .. } | Command::ImportSpecies { .. });
+    // Queries read a legacy file as it is, only the commands that save the type chart migrate it
+    let (mut type_chart, filepath) = load_type_chart(filepath, modifies_type_chart || matches!(command, Command::Tui))?;
+    if let Some(session) = session {
+        type_chart.set_session(session);
+    }
+    let command = match resolve_type_names(command, &type_chart) {
+        Err(err) => {
+            eprintln!("{}", err);
+            return Err(error_to_exit_code(&err));
+        },
+        Ok(command) => command,
+    };
+    let result = match command {
+        Command::AddType { type_name } => {
+            type_chart.add_new_type(&type_name)
+                .map(|()| println!("New type {} added", type_name))
+        },
+        Command::RemoveType { type_name } => {
+            type_chart.remove_existing_type(&type_name)
+                .map(|()| println!("Removed type {}", type_name))
+        },
+        Command::RenameType { type_name, new_type_name } => {
+            type_chart.rename_type(&type_name, &new_type_name)
+                .map(|()| println!("Renamed type {} to {}", type_name, new_type_name))
+        },
+        Command::Merge { type_name, merged_type_name, keep_existing } => {
+            let conflicts = match type_chart.get_merge_conflicts(&type_name, &merged_type_name) {
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Err(error_to_exit_code(&err));
+                },
+                Ok(conflicts) => conflicts,
+            };
+            let resolutions = if keep_existing { Ok(Vec::new()) } else { resolve_merge_conflicts(&conflicts, &type_name, &merged_type_name, &type_chart, &filepath) };
+            let resolutions = match resolutions {
+                Err(Interruption::Back) => {
+                    println!("Merge cancelled");
+                    return Ok(());
+                },
+                Err(Interruption::Exit(0)) => return Ok(()),
+                Err(Interruption::Exit(exit_code)) => return Err(exit_code),
+                Ok(resolutions) => resolutions,
+            };
+            type_chart.merge_types(&type_name, &merged_type_name, &resolutions)
+                .map(|()| println!("Merged type {} into {}", merged_type_name, type_name))
+        },
+        Command::Set { attacking_type, opposing_type, effectiveness, note } => {
+            type_chart.add_effectiveness_with_note(&attacking_type, &opposing_type, effectiveness, note)
+                .map(|()| println!("{} type attacks are now {} against {}", attacking_type, effectiveness, opposing_type))
+        },
+        Command::Show { type_name, neutral } => {
+            type_chart.get_attacking_effectiveness(&type_name).and_then(|attacking_type_effectiveness_map| {
+                let defensing_type_effectiveness_map = type_chart.get_defensive_effectiveness(&type_name)?;
+                print_type_effectiveness_map(&attacking_type_effectiveness_map, &type_name, true, neutral, false);
+                print_type_effectiveness_map(&defensing_type_effectiveness_map, &type_name, false, neutral, false);
+                return Ok(());
+            })
+        },
+        Command::Defend { type_names, neutral, unsure } => {
+            type_chart.get_multiple_defensive_effectiveness(&type_names)
+                .map(|type_effectiveness_map| print_type_effectiveness_map(&type_effectiveness_map, &type_names.join(", "), false, neutral, unsure))
+        },
+        Command::Coverage { type_names, max_defending_types, neutral, unsure } => {
+            type_chart.get_offensive_coverage(&type_names, max_defending_types as usize)
+                .map(|coverage| print_coverage(&coverage, &type_names.join(", "), neutral, unsure))
+        },
+        Command::Chart { types, no_color } => print_type_chart_grid(&type_chart, &types, !no_color),
+        Command::Export { output, types } => {
+            export_type_chart(&type_chart, &types, &output)
+                .map(|()| println!("Type chart exported to {}", output))
+        },
+        Command::Metadata { key: Some(key), value: Some(value) } => {
+            type_chart.set_metadata(&key, &value);
+            Ok(())
+        },
+        Command::Metadata { key: Some(key), value: None } => {
+            if let Some(value) = type_chart.get_metadata().get(&key) {
+                println!("{}", value);
+            }
+            Ok(())
+        },
+        Command::Metadata { key: None, .. } => {
+            for (key, value) in type_chart.get_metadata() {
+                println!("{}: {}", key, value);
+            }
+            Ok(())
+        },
+        Command::Undo { steps } => {
+            undo_changes(&mut type_chart, steps, false);
+            Ok(())
+        },
+        Command::Redo { steps } => {
+            undo_changes(&mut type_chart, steps, true);
+            Ok(())
+        },
+        Command::History => {
+            print_history(&type_chart);
+            Ok(())
+        },
+        Command::Learned { attacking_type, opposing_type } => {
+            files::read_discovery_log(&filepath).map(|discoveries| {
+                let discoveries: Vec<Discovery> = discoveries.into_iter()
+                    .filter(|discovery| attacking_type.as_ref().is_none_or(|attacking_type| &discovery.attacking_type == attacking_type))
+                    .filter(|discovery| opposing_type.as_ref().is_none_or(|opposing_type| &discovery.defending_type == opposing_type))
+                    .collect();
+                print_discoveries(&discoveries);
+            })
+        },
+        Command::Replay { output } => replay_type_chart(&type_chart, &filepath, &output),
+        Command::Restore { backup: None } => print_backups(&filepath),
+        Command::Restore { backup: Some(number) } => {
+            restore_backup(&filepath, number).map(|restored_type_chart| {
+                type_chart = restored_type_chart;
+                println!("Type chart restored from backup {}", number);
+            })
+        },
+        Command::Observe { attacking_type, defending_types, message } => {
+            type_chart.add_observation(&attacking_type, &defending_types, message)
+                .and_then(|()| deduce_matchups(&mut type_chart))
+        },
+        Command::Deduce => deduce_matchups(&mut type_chart),
+        Command::ImportSpecies { input, types } => import_species(&mut type_chart, &input, types.as_ref()),
+        Command::Species { species_name: None, .. } => {
+            print_species(&type_chart);
+            Ok(())
+        },
+        Command::Species { species_name: Some(species_name), remove: true, .. } => {
+            type_chart.remove_species(&species_name)
+                .map(|()| println!("Removed {} from the species list", species_name))
+        },
+        Command::Species { species_name: Some(species_name), type_names, .. } if type_names.is_empty() => {
+            type_chart.get_species_types(&species_name)
+                .map(|species_types| println!("{}: {}", species_name, species_types.join("/")))
+        },
+        Command::Species { species_name: Some(species_name), type_names, .. } => {
+            type_chart.set_species(&species_name, &type_names)
+                .map(|()| println!("{} is now {}", species_name, type_names.join("/")))
+        },
+        Command::Damage { attacking_type, defending_types, level, attack, power, defense, damage: (min_damage, max_damage), hp, stab, critical, yes } => {
+            let observation = DamageObservation { level, attack, power, defense, same_type: stab, critical_hit: critical, defender_hp: hp, min_damage, max_damage };
+            match solve_damage(&mut type_chart, &attacking_type, &defending_types, &observation, !yes, &filepath) {
+                Err(Interruption::Back) | Err(Interruption::Exit(0)) => return Ok(()),
+                Err(Interruption::Exit(exit_code)) => return Err(exit_code),
+                Ok(()) => Ok(()),
+            }
+        },
+        // Leaving with Ctrl-C throws the changes away
+        Command::Tui => run_tui(&mut type_chart).map(|save| modifies_type_chart = save),
+        // Already handled before loading the type chart
+        Command::Migrate { .. } | Command::Import { .. } => Ok(()),
+    };
+    if let Err(err) = result {
+        eprintln!("{}", err);
+        return Err(error_to_exit_code(&err));
+    }
+    // Queries leave the type chart untouched, no need to rewrite the file
+    if !modifies_type_chart {
+        return Ok(());
+    }
+    return save(&type_chart, &filepath);
+}
@@ -0,0 +1,132 @@
+use std::io::IsTerminal;
+
+use tmt2_type_tracker::{Effectiveness, Multiplier, TrackerError, TypeChart};
+
+// Width used when we can't ask the terminal for its size, same as the rest of the CLI
+const DEFAULT_WIDTH: usize = 101;
+const MIN_ABBREVIATION_LENGTH: usize = 3;
+
+const RESET: &str = "\x1b[0m";
+const GREEN: &str = "\x1b[30;42m";
+const RED: &str = "\x1b[30;41m";
+const BLACK: &str = "\x1b[37;40m";
+const YELLOW: &str = "\x1b[30;43m";
+const GREEN_TEXT: &str = "\x1b[32m";
+const RED_TEXT: &str = "\x1b[31m";
+const GREY_TEXT: &str = "\x1b[90m";
+
+// Shortest prefixes (at least 3 letters) that are still different for every type
+pub(super) fn abbreviate_type_names(type_names: &[String]) -> Vec<String> {
+    let mut abbreviations = Vec::new();
+    for type_name in type_names {
+        let mut length = MIN_ABBREVIATION_LENGTH;
+        while length < type_name.chars().count() {
+            let abbreviation: String = type_name.chars().take(length).collect();
+            let is_unique = type_names.iter()
+                .filter(|other_type| *other_type != type_name)
+                .all(|other_type| !other_type.starts_with(&abbreviation));
+            if is_unique {
+                break;
+            }
+            length += 1;
+        }
+        abbreviations.push(type_name.chars().take(length).collect());
+    }
+    return abbreviations;
+}
+
+fn multiplier_symbol_and_colors(multiplier: &Multiplier) -> (String, Option<&'static str>, Option<&'static str>) {
+    match multiplier {
+        Multiplier::Immune => return ("0".to_string(), Some(BLACK), Some(GREY_TEXT)),
+        Multiplier::PowerOfTwo(0) => return (".".to_string(), None, None),
+        multiplier if *multiplier > Multiplier::NEUTRAL => return (format!("{}", multiplier.as_f32()), Some(GREEN), Some(GREEN_TEXT)),
+        multiplier => return (format!("1/{}", 1. / multiplier.as_f32()), Some(RED), Some(RED_TEXT)),
+    }
+}
+
+// Confirmed matchups are written on a colored background, suspected ones are written in color with a star
+pub(super) fn cell_symbol_and_color(effectiveness: &Effectiveness) -> (String, Option<&'static str>) {
+    match effectiveness {
+        Effectiveness::Unknown => return ("?".to_string(), Some(YELLOW)),
+        Effectiveness::Known(multiplier) => {
+            let (symbol, background_color, _) = multiplier_symbol_and_colors(multiplier);
+            return (symbol, background_color);
+        },
+        Effectiveness::Suspected(multiplier) => {
+            let (symbol, _, text_color) = multiplier_symbol_and_colors(multiplier);
+            return (format!("{}*", symbol), text_color);
+        },
+    }
+}
+
+fn paint(text: &str, color: Option<&str>, use_colors: bool) -> String {
+    match color {
+        Some(color) if use_colors => return format!("{}{}{}", color, text, RESET),
+        _ => return text.to_string(),
+    }
+}
+
+fn terminal_width() -> usize {
+    match terminal_size::terminal_size() {
+        Some((terminal_size::Width(width), _)) => return width as usize,
+        None => return DEFAULT_WIDTH,
+    }
+}
+
+// Prints the type chart with the attacking types as rows and the defending types as columns,
+// split into several pages when the columns don't fit in the terminal
+pub fn print_type_chart_grid(type_chart: &TypeChart, type_filter: &[String], use_colors: bool) -> Result<(), TrackerError> {
+    let type_names = if type_filter.is_empty() { type_chart.get_type_list() } else { type_filter.to_vec() };
+    // Checking all types exist before printing anything
+    for type_name in &type_names {
+        type_chart.get_effectiveness(type_name, type_name)?;
+    }
+    if type_names.is_empty() {
+        println!("The type chart is empty");
+        return Ok(());
+    }
+    let use_colors = use_colors && std::io::stdout().is_terminal();
+    let abbreviations = abbreviate_type_names(&type_names);
+    let corner = "Atk \\ Def";
+    let row_header_width = type_names.iter().map(|type_name| type_name.chars().count()).max().unwrap_or(0).max(corner.len());
+    let mut cell_width = abbreviations.iter().map(|abbreviation| abbreviation.chars().count()).max().unwrap_or(0).max(3);
+    for attacking_type in &type_names {
+        for defending_type in &type_names {
+            let (symbol, _) = cell_symbol_and_color(&type_chart.get_effectiveness(attacking_type, defending_type)?);
+            cell_width = cell_width.max(symbol.chars().count());
+        }
+    }
+    let columns_per_page = (terminal_width().saturating_sub(row_header_width + 1) / (cell_width + 1)).max(1);
+
+    let nb_pages = type_names.len().div_ceil(columns_per_page);
+    for (page_index, page_start) in (0..type_names.len()).step_by(columns_per_page).enumerate() {
+        let page_end = (page_start + columns_per_page).min(type_names.len());
+        if nb_pages > 1 {
+            println!("Page {}/{} (defending types {} to {})", page_index + 1, nb_pages, type_names[page_start], type_names[page_end - 1]);
+        }
+        print!("{:>width$} ", corner, width = row_header_width);
+        for abbreviation in &abbreviations[page_start..page_end] {
+            print!("{:^width$} ", abbreviation, width = cell_width);
+        }
+        println!();
+        for attacking_type in &type_names {
+            print!("{:>width$} ", attacking_type, width = row_header_width);
+            for defending_type in &type_names[page_start..page_end] {
+                let effectiveness = type_chart.get_effectiveness(attacking_type, defending_type)?;
+                let (symbol, color) = cell_symbol_and_color(&effectiveness);
+                print!("{} ", paint(&format!("{:^width$}", symbol, width = cell_width), color, use_colors));
+            }
+            println!();
+        }
+        println!();
+    }
+    println!(
+        "Legend : {} Super Effective  {} Not Very Effective  {} Immune   .  Neutral  {} Unknown  {} Suspected",
+        paint(" 2 ", Some(GREEN), use_colors),
+        paint("1/2", Some(RED), use_colors),
+        paint(" 0 ", Some(BLACK), use_colors),
+        paint(" ? ", Some(YELLOW), use_colors),
+        paint("2* ", Some(GREEN_TEXT), use_colors),
+    );
+    return Ok(());
+}
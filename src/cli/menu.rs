@@ -0,0 +1,551 @@
+use rustyline::error::ReadlineError;
+
+use tmt2_type_tracker::{damage, damage::DamageObservation, files, BattleMessage, Discovery, Effectiveness, MergeConflict, TrackerError, TypeChart};
+
+use crate::cli::{EFFECTIVENESS_COMPLETIONS, load_type_chart, parse_damage_range, parse_effectiveness, print_coverage, print_discoveries, print_history, print_type_chart_grid, print_type_effectiveness_map, read_line};
+
+// Why a question was left without an answer
+pub enum Interruption {
+    // The user wants to go back to the menu, the changes made so far are kept
+    Back,
+    // The program ended with this exit code
+    Exit(i32),
+}
+
+// Reads an answer with line editing and tab completion of `answers`
+// Writing back or pressing Ctrl-C returns to the menu, writing quit or pressing Ctrl-D ends the program
+fn read_user_input(answers: &[String], type_chart: &TypeChart, filepath: &String) -> Result<String, Interruption> {
+    match read_line(answers) {
+        Err(ReadlineError::Interrupted) => return Err(Interruption::Back),
+        Err(ReadlineError::Eof) => return Err(end_program(type_chart, filepath)),
+        Err(err) => {
+            eprintln!("{}", err);
+            return Err(Interruption::Exit(1));
+        },
+        Ok(user_input) => match user_input.trim().to_lowercase().as_str() {
+            "back" => return Err(Interruption::Back),
+            "quit" => return Err(end_program(type_chart, filepath)),
+            _ => return Ok(user_input),
+        },
+    }
+}
+
+// Asks until the answer isn't empty, type names are completed
+fn get_info_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<String, Interruption> {
+    let type_names = type_chart.get_type_list();
+    loop  {
+        println!("{}", display_string);
+        let user_input = read_user_input(&type_names, type_chart, filepath)?;
+        if !user_input.trim().is_empty() {
+            return Ok(user_input);
+        }
+    }
+}
+
+// Same as get_info_from_user, but an empty answer is allowed
+fn get_note_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<Option<String>, Interruption> {
+    println!("{}", display_string);
+    match read_user_input(&[], type_chart, filepath)?.trim() {
+        "" => return Ok(None),
+        note => return Ok(Some(note.to_string())),
+    }
+}
+
+fn get_effectiveness_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<Effectiveness, Interruption> {
+    let effectiveness_names: Vec<String> = EFFECTIVENESS_COMPLETIONS.iter().map(|effectiveness_name| effectiveness_name.to_string()).collect();
+    loop  {
+        println!("{}", display_string);
+        println!("1: Super Effective");
+        println!("2: Neutral");
+        println!("3: Not Very Effective");
+        println!("4: Immune");
+        println!("(Add a ? after your answer if you are not sure, e.g. 1?)");
+        let user_input = read_user_input(&effectiveness_names, type_chart, filepath)?;
+        let user_effectiveness = user_input.trim();
+        let choice = user_effectiveness.trim_end_matches('?');
+        // The numbers are only shortcuts for the effectiveness names, the ? marking a suspected effectiveness is kept
+        let effectiveness_name = match choice {
+            "1" => "Super Effective",
+            "2" => "Neutral",
+            "3" => "Not Very Effective",
+            "4" => "Immune",
+            _ => choice,
+        };
+        let effectiveness = parse_effectiveness(&format!("{}{}", effectiveness_name, &user_effectiveness[choice.len()..]));
+        match effectiveness {
+            Ok(effectiveness) => return Ok(effectiveness),
+            Err(_) => println!("That is not a valid effectiveness"),
+        }
+    }
+}
+
+fn get_battle_message_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<BattleMessage, Interruption> {
+    loop  {
+        println!("{}", display_string);
+        println!("1: It's super effective!");
+        println!("2: No message");
+        println!("3: It's not very effective...");
+        println!("4: It doesn't affect the Pokemon...");
+        match read_user_input(&[], type_chart, filepath)?.parse() {
+            Ok(message) => return Ok(message),
+            Err(_) => println!("That is not a valid message"),
+        }
+    }
+}
+
+fn get_number_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<u32, Interruption> {
+    loop {
+        match get_info_from_user(display_string, type_chart, filepath)?.trim().parse() {
+            Ok(number) => return Ok(number),
+            Err(_) => println!("That is not a number"),
+        }
+    }
+}
+
+// Asks for a type of the type chart until the answer matches one, see TypeChart::resolve_type_name
+fn get_type_name_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<String, Interruption> {
+    loop {
+        let type_name = get_info_from_user(display_string, type_chart, filepath)?;
+        match type_chart.resolve_type_name(&type_name) {
+            Err(err) => println!("{}", err),
+            Ok(type_name) => return Ok(type_name),
+        }
+    }
+}
+
+// Asks for types until the user writes none, there is always at least one type
+fn get_type_names_from_user(first_display_string: &str, next_display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<Vec<String>, Interruption> {
+    let type_names = vec![get_type_name_from_user(first_display_string, type_chart, filepath)?];
+    return get_next_type_names_from_user(type_names, next_display_string, type_chart, filepath);
+}
+
+fn get_next_type_names_from_user(mut type_names: Vec<String>, next_display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<Vec<String>, Interruption> {
+    loop {
+        let type_name = get_info_from_user(next_display_string, type_chart, filepath)?;
+        if type_name.trim().eq_ignore_ascii_case("none") {
+            return Ok(type_names);
+        }
+        match type_chart.resolve_type_name(&type_name) {
+            Err(err) => println!("{}", err),
+            Ok(type_name) => type_names.push(type_name),
+        }
+    }
+}
+
+// Asks for a Pokemon or its types, Pokemon missing from the species list can be added on the way
+fn get_defender_types_from_user(display_string: &str, type_chart: &mut TypeChart, filepath: &String) -> Result<Vec<String>, Interruption> {
+    let mut answers = type_chart.get_type_list();
+    answers.extend(type_chart.get_species().into_keys());
+    loop {
+        println!("{}", display_string);
+        let answer = read_user_input(&answers, type_chart, filepath)?;
+        let answer = answer.trim().to_string();
+        if answer.is_empty() {
+            continue;
+        }
+        if let Ok(species_types) = type_chart.get_species_types(&answer) {
+            return Ok(species_types);
+        }
+        match type_chart.resolve_type_name(&answer) {
+            Ok(type_name) => return get_next_type_names_from_user(vec![type_name], "What is the next type? (write none to stop)", type_chart, filepath),
+            Err(err) => println!("{}", err),
+        }
+        if get_bool_from_user(&format!("Is {} a Pokemon you want to add to the species list? (y/n)", answer), type_chart, filepath)? {
+            let species_types = get_type_names_from_user("What is its first type?", "What is its next type? (write none to stop)", type_chart, filepath)?;
+            match type_chart.set_species(&answer, &species_types) {
+                Err(err) => println!("{}", err),
+                Ok(()) => {
+                    println!("Added {} to the species list as {}", answer, species_types.join("/"));
+                    return Ok(species_types);
+                },
+            }
+        }
+    }
+}
+
+fn get_bool_from_user(display_string: &str, type_chart: &TypeChart, filepath: &String) -> Result<bool, Interruption> {
+    let answers = ["yes".to_string(), "no".to_string()];
+    loop {
+        println!("{}", display_string);
+        match read_user_input(&answers, type_chart, filepath)?.trim().to_lowercase().as_str() {
+            "true" | "yes" | "y" => return Ok(true),
+            "false" | "no" | "n" => return Ok(false),
+            _ => {
+                println!("Incorrect option (y/n)");
+                continue;
+            }
+        }
+    }
+}
+
+// Asks which effectiveness to keep for every matchup the merged types disagree on
+pub fn resolve_merge_conflicts(conflicts: &[MergeConflict], type_name: &String, merged_type_name: &String, type_chart: &TypeChart, filepath: &String) -> Result<Vec<(MergeConflict, Effectiveness)>, Interruption> {
+    let mut resolutions = Vec::new();
+    for conflict in conflicts {
+        println!("{} against {}: {} says {}, {} says {}", conflict.attacking_type, conflict.defending_type, type_name, conflict.kept, merged_type_name, conflict.merged);
+        let question = format!("1: Keep {}\n2: Use {}\n(or write the right effectiveness)", conflict.kept, conflict.merged);
+        let effectiveness = loop {
+            match get_info_from_user(&question, type_chart, filepath)?.trim() {
+                "1" => break conflict.kept,
+                "2" => break conflict.merged,
+                answer => match parse_effectiveness(answer) {
+                    Ok(effectiveness) => break effectiveness,
+                    Err(_) => println!("That is not a valid effectiveness"),
+                },
+            }
+        };
+        resolutions.push((conflict.clone(), effectiveness));
+    }
+    return Ok(resolutions);
+}
+
+// Asks whether to save before leaving, going back from this question returns to the menu without leaving
+fn end_program(type_chart: &TypeChart, filepath: &String) -> Interruption {
+    let answers = ["yes".to_string(), "no".to_string()];
+    loop {
+        println!("Do you want to save your changes?(y/n)");
+        let user_input = match read_line(&answers) {
+            Err(ReadlineError::Interrupted) => return Interruption::Back,
+            // Leaving without answering doesn't save anything
+            Err(ReadlineError::Eof) => return Interruption::Exit(0),
+            Err(err) => {
+                eprintln!("{}", err);
+                return Interruption::Exit(1);
+            }
+            Ok(user_input) => user_input,
+        };
+        match user_input.trim().to_lowercase().as_str() {
+            "y" | "yes" => {
+                if let Err(err) = files::save_types_to_file(type_chart, filepath) {
+                    eprintln!("Could not save the type chart to {}:", filepath);
+                    eprintln!("{}", err);
+                    return Interruption::Exit(1);
+                }
+                return Interruption::Exit(0);
+            },
+            "n" | "no" => return Interruption::Exit(0),
+            "back" => return Interruption::Back,
+            _ => println!("Invalid answer"),
+        };
+    }
+}
+
+// Undoes or redoes up to `steps` changes, and tells which ones
+pub fn undo_changes(type_chart: &mut TypeChart, steps: usize, redo: bool) {
+    for _ in 0..steps {
+        let entry = if redo { type_chart.redo() } else { type_chart.undo() };
+        match entry {
+            None => {
+                println!("Nothing left to {}", if redo { "redo" } else { "undo" });
+                return;
+            },
+            Some(entry) => println!("{}: {}", if redo { "Redone" } else { "Undone" }, entry.edit),
+        }
+    }
+}
+
+// Fills the matchups the battle observations give away, after telling which ones and which observations disagree
+pub fn deduce_matchups(type_chart: &mut TypeChart) -> Result<(), TrackerError> {
+    let inference = type_chart.infer_matchups();
+    for contradiction in &inference.contradictions {
+        println!("Contradiction: {}", contradiction);
+    }
+    if inference.deductions.is_empty() {
+        println!("No new matchup could be deduced");
+    }
+    for deduction in &inference.deductions {
+        println!("Deduced {}", deduction);
+    }
+    return type_chart.apply_deductions(&inference.deductions);
+}
+
+// Tells which type multipliers could deal the damage, and offers to save the matchups it gives away
+pub fn solve_damage(type_chart: &mut TypeChart, attacking_type: &String, defending_types: &[String], observation: &DamageObservation, ask: bool, filepath: &String) -> Result<(), Interruption> {
+    let multipliers = damage::possible_multipliers(observation, defending_types.len());
+    let multiplier = match multipliers[..] {
+        [] => {
+            println!("No type multiplier deals this damage, check the stats and the damage");
+            return Ok(());
+        },
+        [multiplier] => multiplier,
+        _ => {
+            let multipliers: Vec<String> = multipliers.iter().map(|multiplier| format!("{} (x{})", multiplier, multiplier.as_f32())).collect();
+            println!("The move could be {}, another damage roll could tell them apart", multipliers.join(" or "));
+            return Ok(());
+        },
+    };
+    println!("The move was {} (x{})", multiplier, multiplier.as_f32());
+    let mut known_multipliers = Vec::new();
+    let mut unknown_types = Vec::new();
+    for defending_type in defending_types {
+        match type_chart.get_effectiveness(attacking_type, defending_type) {
+            Ok(Effectiveness::Known(known_multiplier)) => known_multipliers.push(known_multiplier),
+            _ => unknown_types.push(defending_type.clone()),
+        }
+    }
+    let multipliers = match damage::split_multiplier(multiplier, &known_multipliers, unknown_types.len()) {
+        None if unknown_types.is_empty() => {
+            println!("This doesn't fit the confirmed matchups of {} against {}", attacking_type, defending_types.join("/"));
+            return Ok(());
+        },
+        None => {
+            println!("This isn't enough to tell the matchups of {} against {}", attacking_type, unknown_types.join(" and "));
+            return Ok(());
+        },
+        Some(multipliers) if multipliers.is_empty() => {
+            println!("This fits the type chart");
+            return Ok(());
+        },
+        Some(multipliers) => multipliers,
+    };
+    for (defending_type, multiplier) in unknown_types.iter().zip(&multipliers) {
+        println!("{} against {}: {}", attacking_type, defending_type, multiplier);
+    }
+    if ask && !get_bool_from_user("Do you want to save it in the type chart? (y/n)", type_chart, filepath)? {
+        return Ok(());
+    }
+    for (defending_type, multiplier) in unknown_types.iter().zip(multipliers) {
+        let note = Some("Worked out from the damage dealt".to_string());
+        match type_chart.add_effectiveness_with_note(attacking_type, defending_type, Effectiveness::Known(multiplier), note) {
+            Err(err) => eprintln!("{}", err),
+            Ok(()) => println!("{} type attacks are now {} against {}", attacking_type, multiplier, defending_type),
+        }
+    }
+    return Ok(());
+}
+
+fn process_user_input(type_chart: &mut TypeChart, trimed_user_input: &str, filepath: &String) -> Result<(), Interruption> {
+    match trimed_user_input {
+        "1" => {
+            let type_name = get_info_from_user("Which type would you like to add?", type_chart, filepath)?;
+            match type_chart.add_new_type(&type_name.trim().to_string()) {
+                Err(err) => eprintln!("{}", err),
+                Ok(()) => println!("New type {} added", type_name.trim()),
+            }
+            println!();
+        }
+        "2" => {
+            let type_name = get_type_name_from_user("Which type would you like to remove?", type_chart, filepath)?;
+            match type_chart.remove_existing_type(&type_name) {
+                Err(err) => eprintln!("{}", err),
+                Ok(()) => println!("Removed type {}", type_name),
+            }
+            println!();
+        }
+        "3" => {
+            let type_name = get_type_name_from_user("Which type is the attacking type?", type_chart, filepath)?;
+            let opposing_type_name = get_type_name_from_user("Which type is the opposing type?", type_chart, filepath)?;
+            let effectiveness = get_effectiveness_from_user("What is the effectiveness of the attacking type", type_chart, filepath)?;
+            let note = get_note_from_user("Where did you learn it? (leave empty to skip)", type_chart, filepath)?;
+            match type_chart.add_effectiveness_with_note(&type_name, &opposing_type_name, effectiveness, note) {
+                Err(err) => eprintln!("{}", err),
+                Ok(()) => println!("{} type attacks are now {} against {}", type_name, effectiveness, opposing_type_name),
+            }
+            println!();
+        }
+        "4" => {
+            let type_name = get_type_name_from_user("For what type would you like to see it's type chart?", type_chart, filepath)?;
+            let attacking_type_effectiveness_map = match type_chart.get_attacking_effectiveness(&type_name) {
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Ok(());
+                },
+                Ok(attacking_type_effectiveness_map) => attacking_type_effectiveness_map,
+            };
+            let defensing_type_effectiveness_map = match type_chart.get_defensive_effectiveness(&type_name) {
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Ok(());
+                },
+                Ok(defensing_type_effectiveness_map) => defensing_type_effectiveness_map,
+            };
+            let show_neutral = get_bool_from_user("Do you want Neutral to be shown?", type_chart, filepath)?;
+            print_type_effectiveness_map(&attacking_type_effectiveness_map, &type_name, true, show_neutral, false);
+            print_type_effectiveness_map(&defensing_type_effectiveness_map, &type_name, false, show_neutral, false);
+        }
+        "5" => {
+            let type_names = get_defender_types_from_user("What is the Pokemon, or its first type?", type_chart, filepath)?;
+            let type_effectiveness_map = match type_chart.get_multiple_defensive_effectiveness(&type_names) {
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Ok(());
+                },
+                Ok(type_effectiveness_map) => type_effectiveness_map,
+            };
+            let show_neutral = get_bool_from_user("Do you want Neutral to be shown?", type_chart, filepath)?;
+            let show_unsure = get_bool_from_user("Do you want unsure effectivenesses to be shown?", type_chart, filepath)?;
+            print_type_effectiveness_map(&type_effectiveness_map, &type_names.join(", "), false, show_neutral, show_unsure);
+        },
+        "6" => {
+            let type_names = get_type_names_from_user("What is the first attacking type?", "What is the next attacking type? (write none to stop)", type_chart, filepath)?;
+            let max_defending_types = loop {
+                match get_info_from_user("Up to how many types can the defenders have? (1, 2 or 3)", type_chart, filepath)?.trim() {
+                    "1" => break 1,
+                    "2" => break 2,
+                    "3" => break 3,
+                    _ => println!("Incorrect number of types"),
+                }
+            };
+            let coverage = match type_chart.get_offensive_coverage(&type_names, max_defending_types) {
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Ok(());
+                },
+                Ok(coverage) => coverage,
+            };
+            let show_neutral = get_bool_from_user("Do you want Neutral to be shown?", type_chart, filepath)?;
+            let show_unsure = get_bool_from_user("Do you want unsure effectivenesses to be shown?", type_chart, filepath)?;
+            print_coverage(&coverage, &type_names.join(", "), show_neutral, show_unsure);
+        },
+        "7" => {
+            let type_filter = match get_bool_from_user("Do you want to see every type?", type_chart, filepath)? {
+                true => Vec::new(),
+                false => get_type_names_from_user("What is the first type to show?", "What is the next type to show? (write none to stop)", type_chart, filepath)?,
+            };
+            if let Err(err) = print_type_chart_grid(type_chart, &type_filter, true) {
+                eprintln!("{}", err);
+            }
+        },
+        "8" => print_history(type_chart),
+        "9" | "10" => {
+            let redo = trimed_user_input == "10";
+            let question = if redo { "How many changes do you want to redo?" } else { "How many changes do you want to undo?" };
+            let steps = loop {
+                match get_info_from_user(question, type_chart, filepath)?.trim().parse::<usize>() {
+                    Ok(steps) => break steps,
+                    Err(_) => println!("That is not a number"),
+                }
+            };
+            undo_changes(type_chart, steps, redo);
+            println!();
+        },
+        "11" => {
+            let type_name = get_type_name_from_user("Which type is the attacking type?", type_chart, filepath)?;
+            let opposing_type_name = get_type_name_from_user("Which type is the opposing type?", type_chart, filepath)?;
+            match files::read_discovery_log(filepath) {
+                Err(err) => eprintln!("{}", err),
+                Ok(mut discoveries) => {
+                    // The matchups learned during this session are only logged when the type chart is saved
+                    for discovery in type_chart.get_discoveries() {
+                        if !discoveries.contains(&discovery) {
+                            discoveries.push(discovery);
+                        }
+                    }
+                    let discoveries: Vec<Discovery> = discoveries.into_iter()
+                        .filter(|discovery| discovery.attacking_type == type_name && discovery.defending_type == opposing_type_name)
+                        .collect();
+                    print_discoveries(&discoveries);
+                },
+            }
+            println!();
+        },
+        "12" => {
+            let type_name = get_type_name_from_user("Which type would you like to rename?", type_chart, filepath)?;
+            let new_type_name = get_info_from_user("What is its new name?", type_chart, filepath)?;
+            match type_chart.rename_type(&type_name, &new_type_name.trim().to_string()) {
+                Err(err) => eprintln!("{}", err),
+                Ok(()) => println!("Renamed type {} to {}", type_name, new_type_name.trim()),
+            }
+            println!();
+        },
+        "13" => {
+            let type_name = get_type_name_from_user("Which type should be kept?", type_chart, filepath)?;
+            let merged_type_name = get_type_name_from_user("Which type should be merged into it and removed?", type_chart, filepath)?;
+            let conflicts = match type_chart.get_merge_conflicts(&type_name, &merged_type_name) {
+                Err(err) => {
+                    eprintln!("{}", err);
+                    return Ok(());
+                },
+                Ok(conflicts) => conflicts,
+            };
+            let resolutions = resolve_merge_conflicts(&conflicts, &type_name, &merged_type_name, type_chart, filepath)?;
+            match type_chart.merge_types(&type_name, &merged_type_name, &resolutions) {
+                Err(err) => eprintln!("{}", err),
+                Ok(()) => println!("Merged type {} into {}", merged_type_name, type_name),
+            }
+            println!();
+        },
+        "14" => {
+            let type_name = get_type_name_from_user("Which type was the move?", type_chart, filepath)?;
+            let defending_types = get_defender_types_from_user("Which Pokemon was hit, or what is its first type?", type_chart, filepath)?;
+            let message = get_battle_message_from_user("What did the game say?", type_chart, filepath)?;
+            let result = type_chart.add_observation(&type_name, &defending_types, message)
+                .and_then(|()| deduce_matchups(type_chart));
+            if let Err(err) = result {
+                eprintln!("{}", err);
+            }
+            println!();
+        },
+        "15" => {
+            let type_name = get_type_name_from_user("Which type was the move?", type_chart, filepath)?;
+            let defending_types = get_defender_types_from_user("Which Pokemon was hit, or what is its first type?", type_chart, filepath)?;
+            let level = get_number_from_user("What is the level of the attacker?", type_chart, filepath)?;
+            let attack = get_number_from_user("What is the Attack (or Special Attack) of the attacker?", type_chart, filepath)?;
+            let power = get_number_from_user("What is the power of the move?", type_chart, filepath)?;
+            let defense = get_number_from_user("What is the Defense (or Special Defense) of the Pokemon that was hit?", type_chart, filepath)?;
+            let same_type = get_bool_from_user("Does the attacker have the type of the move?", type_chart, filepath)?;
+            let critical_hit = get_bool_from_user("Was it a critical hit?", type_chart, filepath)?;
+            let (min_damage, max_damage) = loop {
+                match parse_damage_range(&get_info_from_user("How much damage did it deal? (e.g. 30, or 28-33 for several hits)", type_chart, filepath)?) {
+                    Ok(damage_range) => break damage_range,
+                    Err(err) => println!("{}", err),
+                }
+            };
+            let defender_hp = match get_bool_from_user("Did the Pokemon faint?", type_chart, filepath)? {
+                true => Some(get_number_from_user("How much HP did it have left before the hit?", type_chart, filepath)?),
+                false => None,
+            };
+            let observation = DamageObservation { level, attack, power, defense, same_type, critical_hit, defender_hp, min_damage, max_damage };
+            solve_damage(type_chart, &type_name, &defending_types, &observation, true, filepath)?;
+            println!();
+        },
+        "16" => {
+            return Err(end_program(type_chart, filepath));
+        },
+        _ => {
+            println!("Incorrect Option");
+        }
+    }
+    return Ok(());
+}
+
+pub fn run_interactive(filepath: &String, session: &Option<String>) -> Result<(), i32> {
+    let (mut type_chart, filepath) = load_type_chart(filepath, true)?;
+    let filepath = &filepath;
+    if let Some(session) = session {
+        type_chart.set_session(session);
+    }
+
+    println!("Welcome to the TMT2 Type Track!");
+    loop {
+        // Show user the available options
+        println!("What would you like to do?");
+        println!("1: Add a new type");
+        println!("2: Remove an existing type");
+        println!("3: Add a new weakness/resistance");
+        println!("4: See stats about a type");
+        println!("5: See stats about multiple types");
+        println!("6: See the coverage of a moveset");
+        println!("7: See the entire type chart");
+        println!("8: See the history of changes");
+        println!("9: Undo changes");
+        println!("10: Redo changes");
+        println!("11: See when a matchup was learned");
+        println!("12: Rename a type");
+        println!("13: Merge two types");
+        println!("14: Record what the game said about a move");
+        println!("15: Work out a multiplier from the damage dealt");
+        println!("16: Quit");
+        println!("(At any point you can write \"back\" or press Ctrl-C to come back to this menu, and write \"quit\" to quit out of the program)");
+
+        // Take user input
+        let result = read_user_input(&[], &type_chart, filepath)
+            .and_then(|user_input| process_user_input(&mut type_chart, user_input.trim(), filepath));
+        match result {
+            Err(Interruption::Back) => println!(),
+            Err(Interruption::Exit(0)) => return Ok(()),
+            Err(Interruption::Exit(exit_code)) => return Err(exit_code),
+            Ok(()) => (),
+        }
+    }
+}
@@ -0,0 +1,12 @@
+mod commands;
+mod grid;
+mod menu;
+mod print;
+mod prompt;
+mod tui;
+pub use commands::{Command, load_type_chart, parse_damage_range, parse_effectiveness, run_command};
+pub use grid::print_type_chart_grid;
+pub use menu::{deduce_matchups, Interruption, resolve_merge_conflicts, run_interactive, solve_damage, undo_changes};
+pub use print::{print_coverage, print_discoveries, print_history, print_type_effectiveness_map};
+pub use prompt::{EFFECTIVENESS_COMPLETIONS, read_line};
+pub use tui::run_tui;
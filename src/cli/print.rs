@@ -0,0 +1,98 @@
+use tmt2_type_tracker::{Coverage, Discovery, EffectivenessMap, TypeChart};
+
+fn display_type_list(type_list: &Vec<String>) {
+    if !type_list.is_empty() {
+        let mut current_length = 0;
+        for index in 0..(type_list.len() - 1) {
+            let type_name = type_list.get(index).expect("Should not be out of bounds");
+            if current_length + type_name.len() > 101 {
+                println!();
+                current_length = 0;
+            }
+            print!("{}, ", type_name);
+            // Need to take into account the comma and space
+            current_length += type_name.len() + 2;
+        }
+        let last_type = type_list.last().expect("Should not be out of bounds, but like again");
+        if current_length + last_type.len() > 101 {
+            println!();
+        }
+        print!("{}", last_type);
+    }
+    println!();
+}
+
+pub fn print_type_effectiveness_map(type_effectiveness_map: &EffectivenessMap, type_name: &String, attacking: bool, show_neutral: bool, show_unsure: bool) {
+    println!("{}", "=".repeat(101));
+    let attacking = match attacking {
+        true => "attacking",
+        false => "defending"
+    };
+    println!("{} when {} :", type_name.trim(), attacking);
+    for (effectiveness, type_list) in type_effectiveness_map {
+        if effectiveness.is_neutral() && !show_neutral {
+            continue;
+        }
+        if effectiveness.is_unsure() && !show_unsure {
+            continue;
+        }
+        if type_list.is_empty() {
+            continue;
+        }
+        let effectiveness = effectiveness.to_string();
+        let dash_length = 50 - (effectiveness.len() / 2);
+        let dashes = "-".repeat(dash_length);
+        print!("{}{}{}", &dashes, effectiveness, &dashes);
+        if effectiveness.len() % 2 == 0 {
+            print!("-");
+        }
+        println!();
+        display_type_list(type_list);
+    }
+    println!("{}", "=".repeat(101));
+}
+
+pub fn print_coverage(coverage: &Coverage, type_names: &String, show_neutral: bool, show_unsure: bool) {
+    print_type_effectiveness_map(&coverage.best_effectiveness, type_names, true, show_neutral, show_unsure);
+    if coverage.walled.is_empty() {
+        println!("Nothing walls {}", type_names);
+    } else {
+        println!("Walled by :");
+        display_type_list(&coverage.walled);
+    }
+    println!("{}", "=".repeat(101));
+}
+
+pub fn print_discoveries(discoveries: &[Discovery]) {
+    if discoveries.is_empty() {
+        println!("No matchup was learned yet");
+        return;
+    }
+    for discovery in discoveries {
+        print!("{} [{}] {} against {}: {} -> {}", discovery.timestamp.format("%Y-%m-%d %H:%M:%S"), discovery.session,
+            discovery.attacking_type, discovery.defending_type, discovery.previous, discovery.new);
+        match &discovery.note {
+            None => println!(),
+            Some(note) => println!(" ({})", note),
+        }
+    }
+}
+
+pub fn print_history(type_chart: &TypeChart) {
+    let history = type_chart.get_history();
+    let undone_history = type_chart.get_undone_history();
+    if history.is_empty() && undone_history.is_empty() {
+        println!("The type chart wasn't changed yet");
+        return;
+    }
+    println!("Changes made to the type chart, from the oldest to the most recent:");
+    for entry in &history {
+        println!("{}  {}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"), entry.edit);
+    }
+    if !undone_history.is_empty() {
+        println!("Undone changes, from the next one to redo:");
+        for entry in &undone_history {
+            println!("{}  {}", entry.timestamp.format("%Y-%m-%d %H:%M:%S"), entry.edit);
+        }
+    }
+}
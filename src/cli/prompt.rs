@@ -0,0 +1,59 @@
+use std::cell::RefCell;
+
+use rustyline::{completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter, history::DefaultHistory, validate::Validator, Context, Editor, Helper};
+
+/// Labels offered when completing an effectiveness
+pub const EFFECTIVENESS_COMPLETIONS: [&str; 4] = ["Super Effective", "Neutral", "Not Very Effective", "Immune"];
+
+// Completes the whole line with the answers the current question expects
+struct AnswerCompleter {
+    answers: Vec<String>,
+}
+
+impl Completer for AnswerCompleter {
+    type Candidate = String;
+
+    fn complete(&self, line: &str, pos: usize, _ctx: &Context<'_>) -> rustyline::Result<(usize, Vec<String>)> {
+        let typed_answer = line[..pos].to_lowercase();
+        let candidates = self.answers.iter()
+            .filter(|answer| answer.to_lowercase().starts_with(&typed_answer))
+            .cloned()
+            .collect();
+        return Ok((0, candidates));
+    }
+}
+
+impl Hinter for AnswerCompleter {
+    type Hint = String;
+}
+
+impl Highlighter for AnswerCompleter {}
+
+impl Validator for AnswerCompleter {}
+
+impl Helper for AnswerCompleter {}
+
+thread_local! {
+    // The same editor is used for every question, so the history is shared by the whole session
+    static EDITOR: RefCell<Option<Editor<AnswerCompleter, DefaultHistory>>> = const { RefCell::new(None) };
+}
+
+/// Reads a line with history, line editing and tab completion of `answers`
+///
+/// Fails with `ReadlineError::Interrupted` on Ctrl-C and `ReadlineError::Eof` on Ctrl-D or at the end of the input
+pub fn read_line(answers: &[String]) -> Result<String, ReadlineError> {
+    return EDITOR.with(|editor| {
+        let mut editor = editor.borrow_mut();
+        if editor.is_none() {
+            *editor = Some(Editor::new()?);
+        }
+        // Was just created if missing
+        let editor = editor.as_mut().expect("The editor should exist");
+        editor.set_helper(Some(AnswerCompleter { answers: answers.to_vec() }));
+        let line = editor.readline("> ")?;
+        if !line.trim().is_empty() {
+            editor.add_history_entry(line.trim())?;
+        }
+        return Ok(line);
+    });
+}
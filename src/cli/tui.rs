@@ -0,0 +1,313 @@
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers},
+    layout::{Constraint, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    DefaultTerminal, Frame,
+};
+
+use tmt2_type_tracker::{Effectiveness, EffectivenessMap, Multiplier, TrackerError, TypeChart};
+
+use crate::cli::grid::{abbreviate_type_names, cell_symbol_and_color};
+
+const SIDE_PANEL_WIDTH: u16 = 40;
+// Wide enough for the longest cell symbol, 1/4*
+const MIN_CELL_WIDTH: usize = 4;
+const CORNER: &str = "Atk \\ Def";
+const KEYS_HELP: &str = "Arrows: move  1-4: SE/Neutral/NVE/Immune  ?: unsure  x: unknown  u/r: undo/redo  /: search  q: save and quit  Ctrl-C: quit without saving";
+const SEARCH_HELP: &str = "Enter: go to the attacking type  Tab: go to the defending type  Esc: cancel";
+
+// Same colors as the chart command, with a colored background for the confirmed matchups
+fn multiplier_color(multiplier: &Multiplier) -> Option<Color> {
+    match multiplier {
+        Multiplier::Immune => return Some(Color::DarkGray),
+        Multiplier::PowerOfTwo(0) => return None,
+        multiplier if *multiplier > Multiplier::NEUTRAL => return Some(Color::Green),
+        _ => return Some(Color::Red),
+    }
+}
+
+fn cell_style(effectiveness: &Effectiveness) -> Style {
+    match effectiveness {
+        Effectiveness::Unknown => return Style::new().fg(Color::Black).bg(Color::Yellow),
+        Effectiveness::Known(Multiplier::Immune) => return Style::new().fg(Color::White).bg(Color::Black),
+        Effectiveness::Known(multiplier) => match multiplier_color(multiplier) {
+            Some(color) => return Style::new().fg(Color::Black).bg(color),
+            None => return Style::new(),
+        },
+        Effectiveness::Suspected(multiplier) => match multiplier_color(multiplier) {
+            Some(color) => return Style::new().fg(color),
+            None => return Style::new(),
+        },
+    }
+}
+
+// Moves the first visible line or column so that the selected one can be seen
+fn scroll_into_view(first: &mut usize, selected: usize, visible: usize) {
+    if selected < *first {
+        *first = selected;
+    } else if selected >= *first + visible {
+        *first = selected + 1 - visible;
+    }
+}
+
+fn effectiveness_map_lines(title: String, type_effectiveness_map: &EffectivenessMap) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::styled(title, Style::new().add_modifier(Modifier::BOLD))];
+    for (effectiveness, type_list) in type_effectiveness_map {
+        if type_list.is_empty() {
+            continue;
+        }
+        // Almost every matchup is neutral, listing them would hide the others
+        if effectiveness.is_neutral() {
+            lines.push(Line::raw(format!("{}: {} types", effectiveness, type_list.len())));
+            continue;
+        }
+        lines.push(Line::raw(format!("{}: {}", effectiveness, type_list.join(", "))));
+    }
+    lines.push(Line::raw(""));
+    return lines;
+}
+
+struct Tui {
+    type_list: Vec<String>,
+    abbreviations: Vec<String>,
+    // Indexes of the selected attacking and defending types
+    row: usize,
+    column: usize,
+    // First row and column shown, the grid scrolls to keep the selected cell visible
+    first_row: usize,
+    first_column: usize,
+    // Rows shown in the last drawn frame, used to move by pages
+    visible_rows: usize,
+    // Text typed in the search box while it is open
+    search: Option<String>,
+    message: String,
+}
+
+impl Tui {
+    fn new() -> Tui {
+        return Tui {
+            type_list: Vec::new(),
+            abbreviations: Vec::new(),
+            row: 0,
+            column: 0,
+            first_row: 0,
+            first_column: 0,
+            visible_rows: 1,
+            search: None,
+            message: String::new(),
+        };
+    }
+
+    // Types can be added or removed by undoing changes, so the selection is kept within the type list
+    fn refresh(&mut self, type_chart: &TypeChart) {
+        self.type_list = type_chart.get_type_list();
+        self.abbreviations = abbreviate_type_names(&self.type_list);
+        let last_index = self.type_list.len().saturating_sub(1);
+        self.row = self.row.min(last_index);
+        self.column = self.column.min(last_index);
+    }
+
+    fn selected_types(&self) -> Option<(String, String)> {
+        let attacking_type = self.type_list.get(self.row)?;
+        let defending_type = self.type_list.get(self.column)?;
+        return Some((attacking_type.clone(), defending_type.clone()));
+    }
+
+    fn draw(&mut self, frame: &mut Frame, type_chart: &TypeChart) {
+        let [main_area, bottom_area] = Layout::vertical([Constraint::Min(3), Constraint::Length(3)]).areas(frame.area());
+        let [grid_area, side_area] = Layout::horizontal([Constraint::Min(0), Constraint::Length(SIDE_PANEL_WIDTH)]).areas(main_area);
+        self.draw_grid(frame, grid_area, type_chart);
+        self.draw_side_panel(frame, side_area, type_chart);
+        let bottom_lines = match &self.search {
+            Some(search) => vec![Line::raw(format!("Search: {}_", search)), Line::styled(SEARCH_HELP, Style::new().fg(Color::DarkGray))],
+            None => vec![Line::raw(self.message.clone()), Line::styled(KEYS_HELP, Style::new().fg(Color::DarkGray))],
+        };
+        frame.render_widget(Paragraph::new(bottom_lines).wrap(Wrap { trim: true }), bottom_area);
+    }
+
+    fn draw_grid(&mut self, frame: &mut Frame, area: Rect, type_chart: &TypeChart) {
+        let title = match self.selected_types() {
+            None => "Type chart".to_string(),
+            Some((attacking_type, defending_type)) => {
+                let effectiveness = type_chart.get_effectiveness(&attacking_type, &defending_type).unwrap_or(Effectiveness::Unknown);
+                format!("{} against {}: {}", attacking_type, defending_type, effectiveness)
+            },
+        };
+        let block = Block::new().borders(Borders::ALL).title(title);
+        let inner_area = block.inner(area);
+        frame.render_widget(block, area);
+        if self.type_list.is_empty() {
+            frame.render_widget(Paragraph::new("The type chart is empty"), inner_area);
+            return;
+        }
+
+        let row_header_width = self.type_list.iter().map(|type_name| type_name.chars().count()).max().unwrap_or(0).max(CORNER.len());
+        let cell_width = self.abbreviations.iter().map(|abbreviation| abbreviation.chars().count()).max().unwrap_or(0).max(MIN_CELL_WIDTH);
+        let visible_columns = ((inner_area.width as usize).saturating_sub(row_header_width + 1) / (cell_width + 1)).max(1);
+        self.visible_rows = (inner_area.height as usize).saturating_sub(1).max(1);
+        scroll_into_view(&mut self.first_row, self.row, self.visible_rows);
+        scroll_into_view(&mut self.first_column, self.column, visible_columns);
+        let last_row = (self.first_row + self.visible_rows).min(self.type_list.len());
+        let last_column = (self.first_column + visible_columns).min(self.type_list.len());
+
+        let selected_header = Style::new().add_modifier(Modifier::BOLD | Modifier::REVERSED);
+        let mut header = vec![Span::raw(format!("{:>width$} ", CORNER, width = row_header_width))];
+        for column in self.first_column..last_column {
+            let style = if column == self.column { selected_header } else { Style::new() };
+            header.push(Span::styled(format!("{:^width$}", self.abbreviations[column], width = cell_width), style));
+            header.push(Span::raw(" "));
+        }
+        let mut lines = vec![Line::from(header)];
+        for row in self.first_row..last_row {
+            let style = if row == self.row { selected_header } else { Style::new() };
+            let mut spans = vec![Span::styled(format!("{:>width$}", self.type_list[row], width = row_header_width), style), Span::raw(" ")];
+            for column in self.first_column..last_column {
+                let effectiveness = type_chart.get_effectiveness(&self.type_list[row], &self.type_list[column]).unwrap_or(Effectiveness::Unknown);
+                let (symbol, _) = cell_symbol_and_color(&effectiveness);
+                let mut style = cell_style(&effectiveness);
+                if row == self.row && column == self.column {
+                    style = style.add_modifier(Modifier::REVERSED | Modifier::BOLD);
+                }
+                spans.push(Span::styled(format!("{:^width$}", symbol, width = cell_width), style));
+                spans.push(Span::raw(" "));
+            }
+            lines.push(Line::from(spans));
+        }
+        frame.render_widget(Paragraph::new(lines), inner_area);
+    }
+
+    fn draw_side_panel(&self, frame: &mut Frame, area: Rect, type_chart: &TypeChart) {
+        let mut lines = Vec::new();
+        if let Some((attacking_type, defending_type)) = self.selected_types() {
+            if let Ok(attacking_type_effectiveness_map) = type_chart.get_attacking_effectiveness(&attacking_type) {
+                lines.extend(effectiveness_map_lines(format!("{} when attacking", attacking_type), &attacking_type_effectiveness_map));
+            }
+            if let Ok(defensing_type_effectiveness_map) = type_chart.get_defensive_effectiveness(&defending_type) {
+                lines.extend(effectiveness_map_lines(format!("{} when defending", defending_type), &defensing_type_effectiveness_map));
+            }
+        }
+        let panel = Paragraph::new(lines).wrap(Wrap { trim: false }).block(Block::new().borders(Borders::ALL).title("Matchups"));
+        frame.render_widget(panel, area);
+    }
+
+    fn set_selected_effectiveness(&mut self, type_chart: &mut TypeChart, effectiveness: Effectiveness) {
+        let Some((attacking_type, defending_type)) = self.selected_types() else {
+            return;
+        };
+        match type_chart.add_effectiveness(&attacking_type, &defending_type, effectiveness) {
+            Err(err) => self.message = err.to_string(),
+            Ok(()) => self.message = format!("{} type attacks are now {} against {}", attacking_type, effectiveness, defending_type),
+        }
+    }
+
+    // Returns whether to save the type chart once the user leaves, or None to keep going
+    fn handle_key(&mut self, key: KeyEvent, type_chart: &mut TypeChart) -> Option<bool> {
+        let last_index = self.type_list.len().saturating_sub(1);
+        let current_effectiveness = self.selected_types()
+            .and_then(|(attacking_type, defending_type)| type_chart.get_effectiveness(&attacking_type, &defending_type).ok())
+            .unwrap_or(Effectiveness::Unknown);
+        match key.code {
+            KeyCode::Char('q') => return Some(true),
+            KeyCode::Up | KeyCode::Char('k') => self.row = self.row.saturating_sub(1),
+            KeyCode::Down | KeyCode::Char('j') => self.row = (self.row + 1).min(last_index),
+            KeyCode::Left | KeyCode::Char('h') => self.column = self.column.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => self.column = (self.column + 1).min(last_index),
+            KeyCode::PageUp => self.row = self.row.saturating_sub(self.visible_rows),
+            KeyCode::PageDown => self.row = (self.row + self.visible_rows).min(last_index),
+            KeyCode::Home => self.column = 0,
+            KeyCode::End => self.column = last_index,
+            KeyCode::Char('1') => self.set_selected_effectiveness(type_chart, Effectiveness::SUPER_EFFECTIVE),
+            KeyCode::Char('2') => self.set_selected_effectiveness(type_chart, Effectiveness::NEUTRAL),
+            KeyCode::Char('3') => self.set_selected_effectiveness(type_chart, Effectiveness::NOT_VERY_EFFECTIVE),
+            KeyCode::Char('4') => self.set_selected_effectiveness(type_chart, Effectiveness::IMMUNE),
+            KeyCode::Char('x') | KeyCode::Delete | KeyCode::Backspace => self.set_selected_effectiveness(type_chart, Effectiveness::Unknown),
+            KeyCode::Char('?') => match current_effectiveness {
+                Effectiveness::Known(multiplier) => self.set_selected_effectiveness(type_chart, Effectiveness::Suspected(multiplier)),
+                Effectiveness::Suspected(multiplier) => self.set_selected_effectiveness(type_chart, Effectiveness::Known(multiplier)),
+                Effectiveness::Unknown => self.message = "Set an effectiveness before marking it as unsure".to_string(),
+            },
+            KeyCode::Char('u') => match type_chart.undo() {
+                None => self.message = "Nothing left to undo".to_string(),
+                Some(entry) => self.message = format!("Undone: {}", entry.edit),
+            },
+            KeyCode::Char('r') => match type_chart.redo() {
+                None => self.message = "Nothing left to redo".to_string(),
+                Some(entry) => self.message = format!("Redone: {}", entry.edit),
+            },
+            KeyCode::Char('/') => self.search = Some(String::new()),
+            _ => (),
+        }
+        return None;
+    }
+
+    // Types starting with the search come first, then the ones containing it, then the ones with a close name
+    fn find_type(&self, search: &str, type_chart: &TypeChart) -> Option<usize> {
+        let search = search.trim().to_lowercase();
+        if search.is_empty() {
+            return None;
+        }
+        let lowercase_types: Vec<String> = self.type_list.iter().map(|type_name| type_name.to_lowercase()).collect();
+        return lowercase_types.iter().position(|type_name| type_name.starts_with(&search))
+            .or_else(|| lowercase_types.iter().position(|type_name| type_name.contains(&search)))
+            .or_else(|| {
+                let suggestion = type_chart.suggest_type_names(&search).into_iter().next()?;
+                return self.type_list.iter().position(|type_name| *type_name == suggestion);
+            });
+    }
+
+    fn handle_search_key(&mut self, key: KeyEvent, type_chart: &TypeChart) {
+        let Some(search) = &mut self.search else {
+            return;
+        };
+        match key.code {
+            KeyCode::Char(character) => search.push(character),
+            KeyCode::Backspace => {
+                search.pop();
+            },
+            KeyCode::Esc => self.search = None,
+            KeyCode::Enter | KeyCode::Tab => {
+                let search = self.search.take().unwrap_or_default();
+                match self.find_type(&search, type_chart) {
+                    None => self.message = format!("No type matches {}", search),
+                    Some(index) if key.code == KeyCode::Enter => self.row = index,
+                    Some(index) => self.column = index,
+                }
+            },
+            _ => (),
+        }
+    }
+}
+
+fn run_event_loop(terminal: &mut DefaultTerminal, type_chart: &mut TypeChart) -> Result<bool, TrackerError> {
+    let mut tui = Tui::new();
+    loop {
+        tui.refresh(type_chart);
+        terminal.draw(|frame| tui.draw(frame, type_chart))?;
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+            return Ok(false);
+        }
+        if tui.search.is_some() {
+            tui.handle_search_key(key, type_chart);
+        } else if let Some(save) = tui.handle_key(key, type_chart) {
+            return Ok(save);
+        }
+    }
+}
+
+/// Browses and edits the type chart in a full screen grid until the user leaves,
+/// returns whether the changes should be saved
+pub fn run_tui(type_chart: &mut TypeChart) -> Result<bool, TrackerError> {
+    let mut terminal = ratatui::try_init()?;
+    let result = run_event_loop(&mut terminal, type_chart);
+    // The terminal has to be given back even if something went wrong
+    ratatui::try_restore()?;
+    return result;
+}
@@ -0,0 +1,190 @@
+use crate::type_chart::Multiplier;
+
+// Emerald stores type multipliers in tenths, and the damage roll in percents
+const SUPER_EFFECTIVE_TENTHS: u64 = 20;
+const NOT_VERY_EFFECTIVE_TENTHS: u64 = 5;
+const MIN_ROLL_PERCENT: u64 = 85;
+
+/// An attack whose damage was seen in battle, with the stats it was dealt with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DamageObservation {
+    /// Level of the attacker
+    pub level: u32,
+    /// Attack or Special Attack of the attacker, depending on the move
+    pub attack: u32,
+    /// Base power of the move
+    pub power: u32,
+    /// Defense or Special Defense of the defender, depending on the move
+    pub defense: u32,
+    /// The move has one of the types of the attacker
+    pub same_type: bool,
+    /// The hit was a critical hit
+    pub critical_hit: bool,
+    /// HP the defender had before the hit, the damage shown can't be higher
+    pub defender_hp: Option<u32>,
+    /// Lowest and highest damage seen for this attack, the same number when it was only seen once
+    pub min_damage: u32,
+    /// Highest damage seen, see `min_damage`
+    pub max_damage: u32,
+}
+
+impl DamageObservation {
+    // Damage before the type multiplier and the random roll, in the same order and with the same roundings as the game
+    fn base_damage(&self) -> u64 {
+        let level_factor = 2 * self.level as u64 / 5 + 2;
+        let damage = (self.attack as u64).saturating_mul(self.power as u64).saturating_mul(level_factor) / (self.defense as u64).max(1) / 50;
+        // The game deals at least 1 damage before adding 2
+        let mut damage = damage.max(1) + 2;
+        if self.critical_hit {
+            damage = damage.saturating_mul(2);
+        }
+        if self.same_type {
+            damage = damage.saturating_mul(15) / 10;
+        }
+        return damage;
+    }
+
+    /// Lowest and highest damage the attack deals with this type multiplier
+    ///
+    /// The game applies the multiplier of every type one after the other, so the multiplier is applied as several x2 or x1/2 steps
+    pub fn damage_range(&self, multiplier: Multiplier) -> (u32, u32) {
+        let exponent = match multiplier {
+            Multiplier::Immune => return (0, 0),
+            Multiplier::PowerOfTwo(exponent) => exponent,
+        };
+        let mut damage = self.base_damage();
+        let step = if exponent > 0 { SUPER_EFFECTIVE_TENTHS } else { NOT_VERY_EFFECTIVE_TENTHS };
+        for _ in 0..exponent.unsigned_abs() {
+            // A move that isn't immune always deals at least 1 damage
+            damage = (damage.saturating_mul(step) / 10).max(1);
+        }
+        let min_damage = (damage.saturating_mul(MIN_ROLL_PERCENT) / 100).max(1);
+        let hp = self.defender_hp.map(u64::from).unwrap_or(u64::MAX);
+        return (min_damage.min(hp).min(u32::MAX as u64) as u32, damage.min(hp).min(u32::MAX as u64) as u32);
+    }
+}
+
+/// Returns every type multiplier against a Pokemon with `nb_defending_types` types that could deal the observed damage,
+/// from the least to the most effective
+pub fn possible_multipliers(observation: &DamageObservation, nb_defending_types: usize) -> Vec<Multiplier> {
+    let max_exponent = nb_defending_types.min(i8::MAX as usize) as i8;
+    return std::iter::once(Multiplier::Immune)
+        .chain((-max_exponent..=max_exponent).map(Multiplier::PowerOfTwo))
+        .filter(|multiplier| {
+            let (min_damage, max_damage) = observation.damage_range(*multiplier);
+            return min_damage <= observation.max_damage && observation.min_damage <= max_damage;
+        })
+        .collect();
+}
+
+/// Works out the multipliers of the `nb_unknown` unknown matchups of a move from its whole multiplier and the `known` ones
+///
+/// Single matchups go from immune to 2x, so this returns None when several combinations fit or when none does,
+/// and an empty list when the known matchups already give the whole multiplier
+pub fn split_multiplier(multiplier: Multiplier, known: &[Multiplier], nb_unknown: usize) -> Option<Vec<Multiplier>> {
+    let known_immune = known.contains(&Multiplier::Immune);
+    let exponent = match multiplier {
+        Multiplier::Immune if known_immune && nb_unknown == 0 => return Some(Vec::new()),
+        Multiplier::Immune if !known_immune && nb_unknown == 1 => return Some(vec![Multiplier::Immune]),
+        Multiplier::Immune => return None,
+        // An immune matchup would make the whole move immune
+        Multiplier::PowerOfTwo(_) if known_immune => return None,
+        Multiplier::PowerOfTwo(exponent) => exponent,
+    };
+    let known_exponent: i8 = known.iter().map(|known_multiplier| match known_multiplier {
+        Multiplier::PowerOfTwo(known_exponent) => *known_exponent,
+        Multiplier::Immune => 0,
+    }).fold(0, i8::saturating_add);
+    let remaining_exponent = exponent.saturating_sub(known_exponent);
+    match nb_unknown {
+        1 if remaining_exponent == 0 => return Some(vec![Multiplier::NEUTRAL]),
+        // Only the most extreme multipliers can be split between several matchups, 4x is always 2x and 2x
+        nb_unknown if remaining_exponent.unsigned_abs() as usize == nb_unknown => {
+            return Some(vec![Multiplier::PowerOfTwo(remaining_exponent.signum()); nb_unknown]);
+        },
+        _ => return None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Level 50 attacker with 100 Attack using an 80 power move on a defender with 100 Defense
+    fn observation(min_damage: u32, max_damage: u32) -> DamageObservation {
+        return DamageObservation { level: 50, attack: 100, power: 80, defense: 100, same_type: false, critical_hit: false, defender_hp: None, min_damage, max_damage };
+    }
+
+    #[test]
+    fn damage_rolls_match_the_game() {
+        let attack = observation(0, 0);
+        assert_eq!(attack.damage_range(Multiplier::NEUTRAL), (31, 37));
+        assert_eq!(attack.damage_range(Multiplier::SUPER_EFFECTIVE), (62, 74));
+        assert_eq!(attack.damage_range(Multiplier::PowerOfTwo(2)), (125, 148));
+        assert_eq!(attack.damage_range(Multiplier::NOT_VERY_EFFECTIVE), (15, 18));
+        assert_eq!(attack.damage_range(Multiplier::PowerOfTwo(-2)), (7, 9));
+        assert_eq!(attack.damage_range(Multiplier::IMMUNE), (0, 0));
+    }
+
+    #[test]
+    fn same_type_and_critical_hits_are_applied_before_the_multiplier() {
+        let same_type_attack = DamageObservation { same_type: true, ..observation(0, 0) };
+        assert_eq!(same_type_attack.damage_range(Multiplier::NEUTRAL), (46, 55));
+        assert_eq!(same_type_attack.damage_range(Multiplier::SUPER_EFFECTIVE), (93, 110));
+        let critical_hit = DamageObservation { critical_hit: true, ..observation(0, 0) };
+        assert_eq!(critical_hit.damage_range(Multiplier::NEUTRAL), (62, 74));
+        let both = DamageObservation { same_type: true, critical_hit: true, ..observation(0, 0) };
+        assert_eq!(both.damage_range(Multiplier::NEUTRAL), (94, 111));
+    }
+
+    #[test]
+    fn observed_damage_gives_the_multiplier() {
+        assert_eq!(possible_multipliers(&observation(35, 35), 2), vec![Multiplier::NEUTRAL]);
+        assert_eq!(possible_multipliers(&observation(62, 70), 2), vec![Multiplier::SUPER_EFFECTIVE]);
+        assert_eq!(possible_multipliers(&observation(130, 130), 2), vec![Multiplier::PowerOfTwo(2)]);
+        // No roll deals 50 damage
+        assert!(possible_multipliers(&observation(50, 50), 2).is_empty());
+    }
+
+    #[test]
+    fn damage_capped_by_the_hp_left_is_ambiguous_between_2x_and_4x() {
+        let knock_out = DamageObservation { defender_hp: Some(50), ..observation(50, 50) };
+        let multipliers = possible_multipliers(&knock_out, 2);
+        assert_eq!(multipliers, vec![Multiplier::SUPER_EFFECTIVE, Multiplier::PowerOfTwo(2)]);
+        // 4x can only be 2x and 2x, but 2x could come from either type
+        assert_eq!(split_multiplier(Multiplier::PowerOfTwo(2), &[], 2), Some(vec![Multiplier::SUPER_EFFECTIVE; 2]));
+        assert_eq!(split_multiplier(Multiplier::SUPER_EFFECTIVE, &[], 2), None);
+        assert_eq!(split_multiplier(Multiplier::SUPER_EFFECTIVE, &[Multiplier::NEUTRAL], 1), Some(vec![Multiplier::SUPER_EFFECTIVE]));
+        assert_eq!(split_multiplier(Multiplier::PowerOfTwo(2), &[Multiplier::SUPER_EFFECTIVE], 1), Some(vec![Multiplier::SUPER_EFFECTIVE]));
+    }
+
+    #[test]
+    fn a_single_damage_is_never_immune() {
+        // Weak enough that 1/2x and 1/4x both deal the minimum of 1 damage
+        let weak_attack = DamageObservation { level: 5, attack: 5, power: 10, defense: 100, ..observation(1, 1) };
+        assert_eq!(weak_attack.damage_range(Multiplier::NOT_VERY_EFFECTIVE), (1, 1));
+        assert_eq!(weak_attack.damage_range(Multiplier::PowerOfTwo(-2)), (1, 1));
+        // The game rounds the damage up to 1 before adding 2, so the neutral roll goes from 2 to 3
+        assert_eq!(weak_attack.damage_range(Multiplier::NEUTRAL), (2, 3));
+        assert_eq!(possible_multipliers(&weak_attack, 2), vec![Multiplier::PowerOfTwo(-2), Multiplier::NOT_VERY_EFFECTIVE]);
+        let no_damage = DamageObservation { min_damage: 0, max_damage: 0, ..weak_attack };
+        assert_eq!(possible_multipliers(&no_damage, 2), vec![Multiplier::IMMUNE]);
+    }
+
+    #[test]
+    fn immune_splits_only_with_a_single_unknown_matchup() {
+        assert_eq!(split_multiplier(Multiplier::IMMUNE, &[], 1), Some(vec![Multiplier::IMMUNE]));
+        assert_eq!(split_multiplier(Multiplier::IMMUNE, &[Multiplier::IMMUNE], 1), None);
+        assert_eq!(split_multiplier(Multiplier::IMMUNE, &[Multiplier::IMMUNE], 0), Some(Vec::new()));
+        assert_eq!(split_multiplier(Multiplier::IMMUNE, &[Multiplier::NEUTRAL], 1), Some(vec![Multiplier::IMMUNE]));
+        assert_eq!(split_multiplier(Multiplier::PowerOfTwo(-2), &[], 2), Some(vec![Multiplier::NOT_VERY_EFFECTIVE; 2]));
+        assert_eq!(split_multiplier(Multiplier::NEUTRAL, &[Multiplier::IMMUNE], 1), None);
+    }
+
+    #[test]
+    fn huge_stats_and_multipliers_saturate_instead_of_overflowing() {
+        let attack = DamageObservation { level: u32::MAX, attack: u32::MAX, power: u32::MAX, defense: 1, ..observation(u32::MAX, u32::MAX) };
+        assert_eq!(attack.damage_range(Multiplier::PowerOfTwo(i8::MAX)).1, u32::MAX);
+        assert_eq!(possible_multipliers(&attack, 200).last(), Some(&Multiplier::PowerOfTwo(i8::MAX)));
+    }
+}
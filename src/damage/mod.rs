@@ -0,0 +1,2 @@
+mod damage;
+pub use damage::{DamageObservation, possible_multipliers, split_multiplier};
@@ -0,0 +1,102 @@
+use std::{fmt, io};
+
+/// Everything that can go wrong while editing, loading or saving a type chart
+#[derive(Debug)]
+pub enum TrackerError {
+    /// No type has this name
+    UnknownType(String),
+    /// The name the user typed could be several types
+    AmbiguousType {
+        /// Name the user typed
+        type_name: String,
+        /// Types the name could be
+        candidates: Vec<String>,
+    },
+    /// No type has the name the user typed, but some have close names
+    MisspelledType {
+        /// Name the user typed
+        type_name: String,
+        /// Types with a close name, the closest first
+        suggestions: Vec<String>,
+    },
+    /// A type with this name already exists
+    DuplicateType(String),
+    /// The text is neither an effectiveness nor a multiplier
+    UnknownEffectiveness(String),
+    /// The text is not one of the messages shown after an attack
+    UnknownBattleMessage(String),
+    /// No species has this name
+    UnknownSpecies(String),
+    /// Lines and columns start at 1, the column is missing when the whole row is wrong
+    MalformedRow {
+        /// Line of the row
+        line: usize,
+        /// Column of the wrong cell
+        column: Option<usize>,
+        /// What is wrong with the row
+        reason: String,
+    },
+    /// A file could not be read or written
+    Io(io::Error),
+    /// A CSV file could not be read or written
+    Csv(csv::Error),
+    /// A JSON file could not be read or written
+    Json(serde_json::Error),
+    /// The chart file was written by a newer version of the tracker
+    UnsupportedFormatVersion(u32),
+    /// The type chart could not be turned into an image
+    Export(String),
+    /// Backups are numbered from 1, the most recent one
+    UnknownBackup(usize),
+}
+
+impl fmt::Display for TrackerError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TrackerError::UnknownType(type_name) => write!(f, "Type {} doesn't exist!", type_name),
+            TrackerError::AmbiguousType { type_name, candidates } => write!(f, "Type {} could be any of {}", type_name, candidates.join(", ")),
+            TrackerError::MisspelledType { type_name, suggestions } => write!(f, "Type {} doesn't exist! Did you mean {}?", type_name, suggestions.join(" or ")),
+            TrackerError::DuplicateType(type_name) => write!(f, "Type {} is already in the type chart", type_name),
+            TrackerError::UnknownEffectiveness(effectiveness) => write!(f, "Effectiveness {} doesn't exist", effectiveness),
+            TrackerError::UnknownSpecies(species_name) => write!(f, "Pokemon {} isn't in the species list", species_name),
+            TrackerError::UnknownBattleMessage(message) => write!(f, "Battle message {} doesn't exist (SE, Neutral, NVE or Immune)", message),
+            TrackerError::MalformedRow { line, column: Some(column), reason } => write!(f, "Error on line {}, column {}: {}", line, column, reason),
+            TrackerError::MalformedRow { line, column: None, reason } => write!(f, "Error on line {}: {}", line, reason),
+            TrackerError::Io(err) => write!(f, "{}", err),
+            TrackerError::Csv(err) => write!(f, "{}", err),
+            TrackerError::Json(err) => write!(f, "{}", err),
+            TrackerError::UnsupportedFormatVersion(version) => write!(f, "The chart file uses format version {}, which is newer than this tracker supports", version),
+            TrackerError::Export(reason) => write!(f, "Could not export the type chart: {}", reason),
+            TrackerError::UnknownBackup(number) => write!(f, "Backup {} doesn't exist!", number),
+        }
+    }
+}
+
+impl std::error::Error for TrackerError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            TrackerError::Io(err) => Some(err),
+            TrackerError::Csv(err) => Some(err),
+            TrackerError::Json(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for TrackerError {
+    fn from(err: io::Error) -> TrackerError {
+        return TrackerError::Io(err);
+    }
+}
+
+impl From<serde_json::Error> for TrackerError {
+    fn from(err: serde_json::Error) -> TrackerError {
+        return TrackerError::Json(err);
+    }
+}
+
+impl From<csv::Error> for TrackerError {
+    fn from(err: csv::Error) -> TrackerError {
+        return TrackerError::Csv(err);
+    }
+}
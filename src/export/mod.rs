@@ -0,0 +1,6 @@
+#[cfg(feature = "png")]
+mod png;
+mod svg;
+#[cfg(feature = "png")]
+pub use png::save_type_chart_png;
+pub use svg::{save_type_chart_svg, type_chart_to_svg};
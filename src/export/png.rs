@@ -0,0 +1,28 @@
+use resvg::{tiny_skia, usvg};
+
+use crate::{error::TrackerError, export::type_chart_to_svg, type_chart::TypeChart};
+
+/// Saves the type chart drawn by [`type_chart_to_svg`] in a PNG file
+pub fn save_type_chart_png(type_chart: &TypeChart, type_filter: &[String], filepath: &String) -> Result<(), TrackerError> {
+    let svg = type_chart_to_svg(type_chart, type_filter)?;
+    let mut options = usvg::Options::default();
+    // Without fonts, the type names would not be drawn at all
+    let fontdb = options.fontdb_mut();
+    fontdb.load_system_fonts();
+    // The generic sans-serif family defaults to Arial, which is missing on most Linux systems
+    let sans_serif_family = fontdb.faces()
+        .flat_map(|face| face.families.iter().map(|(family, _)| family.clone()))
+        .find(|family| family.contains("Sans") && !family.contains("Mono"));
+    if let Some(sans_serif_family) = sans_serif_family {
+        fontdb.set_sans_serif_family(sans_serif_family);
+    }
+    let tree = usvg::Tree::from_str(&svg, &options).map_err(|err| TrackerError::Export(err.to_string()))?;
+    let size = tree.size().to_int_size();
+    let mut pixmap = match tiny_skia::Pixmap::new(size.width(), size.height()) {
+        None => return Err(TrackerError::Export("The type chart is too big to be drawn".to_string())),
+        Some(pixmap) => pixmap,
+    };
+    resvg::render(&tree, tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    pixmap.save_png(filepath).map_err(|err| TrackerError::Export(err.to_string()))?;
+    return Ok(());
+}
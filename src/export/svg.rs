@@ -0,0 +1,161 @@
+use std::fmt::Write;
+
+use crate::{error::TrackerError, type_chart::{Effectiveness, Multiplier, TypeChart}};
+
+const CELL_SIZE: usize = 30;
+const MARGIN: usize = 10;
+const TITLE_HEIGHT: usize = 30;
+const LEGEND_HEIGHT: usize = 50;
+const LEGEND_ENTRY_WIDTH: usize = 160;
+// Rough width of a character with the font size we use, to size the headers
+const CHAR_WIDTH: usize = 8;
+
+const SUPER_EFFECTIVE_COLOR: &str = "#4caf50";
+const NOT_VERY_EFFECTIVE_COLOR: &str = "#e53935";
+const IMMUNE_COLOR: &str = "#212121";
+const NEUTRAL_COLOR: &str = "#f5f5f5";
+const UNKNOWN_COLOR: &str = "url(#unknown)";
+const SUSPECTED_OPACITY: f32 = 0.45;
+
+fn escape_xml(text: &str) -> String {
+    return text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;").replace('\'', "&apos;");
+}
+
+fn multiplier_style(multiplier: &Multiplier) -> (&'static str, String) {
+    match multiplier {
+        Multiplier::Immune => return (IMMUNE_COLOR, "0".to_string()),
+        Multiplier::PowerOfTwo(0) => return (NEUTRAL_COLOR, String::new()),
+        Multiplier::PowerOfTwo(-1) => return (NOT_VERY_EFFECTIVE_COLOR, "½".to_string()),
+        Multiplier::PowerOfTwo(-2) => return (NOT_VERY_EFFECTIVE_COLOR, "¼".to_string()),
+        multiplier if *multiplier > Multiplier::NEUTRAL => return (SUPER_EFFECTIVE_COLOR, format!("{}", multiplier.as_f32())),
+        multiplier => return (NOT_VERY_EFFECTIVE_COLOR, format!("1/{}", 1. / multiplier.as_f32())),
+    }
+}
+
+fn write_cell(svg: &mut String, x: usize, y: usize, effectiveness: &Effectiveness) {
+    // Suspected matchups are drawn faded with a star next to their multiplier
+    let (fill, symbol, opacity) = match effectiveness {
+        Effectiveness::Unknown => (UNKNOWN_COLOR, "?".to_string(), 1.),
+        Effectiveness::Known(multiplier) => {
+            let (fill, symbol) = multiplier_style(multiplier);
+            (fill, symbol, 1.)
+        },
+        Effectiveness::Suspected(multiplier) => {
+            let (fill, symbol) = multiplier_style(multiplier);
+            (fill, format!("{}*", symbol), SUSPECTED_OPACITY)
+        },
+    };
+    let text_color = if fill == IMMUNE_COLOR { "#ffffff" } else { "#000000" };
+    let _ = write!(svg, r##"<rect x="{}" y="{}" width="{}" height="{}" fill="{}" fill-opacity="{}" stroke="#9e9e9e"/>"##, x, y, CELL_SIZE, CELL_SIZE, fill, opacity);
+    if !symbol.is_empty() {
+        let _ = write!(svg, r#"<text x="{}" y="{}" text-anchor="middle" dominant-baseline="central" font-weight="bold" fill="{}">{}</text>"#,
+            x + CELL_SIZE / 2, y + CELL_SIZE / 2, text_color, symbol);
+    }
+    svg.push('\n');
+}
+
+/// Draws the type chart as an SVG grid, with the attacking types as rows and the defending types as columns
+///
+/// Only the types in `type_filter` are drawn, or every type if it is empty
+pub fn type_chart_to_svg(type_chart: &TypeChart, type_filter: &[String]) -> Result<String, TrackerError> {
+    let type_names = if type_filter.is_empty() { type_chart.get_type_list() } else { type_filter.to_vec() };
+    let corner = "Attacking \\ Defending";
+    let longest_name = type_names.iter().map(|type_name| type_name.chars().count()).max().unwrap_or(0);
+    let grid_x = MARGIN + longest_name.max(corner.len()) * CHAR_WIDTH + MARGIN;
+    let grid_y = MARGIN + TITLE_HEIGHT + longest_name * CHAR_WIDTH + MARGIN;
+    let grid_size = type_names.len() * CELL_SIZE;
+    let width = (grid_x + grid_size).max(MARGIN + 6 * LEGEND_ENTRY_WIDTH) + MARGIN;
+    let height = grid_y + grid_size + LEGEND_HEIGHT + MARGIN;
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{}" viewBox="0 0 {} {}" font-family="DejaVu Sans, Arial, sans-serif" font-size="13">"#, width, height, width, height);
+    let _ = writeln!(svg, r##"<defs><pattern id="unknown" width="8" height="8" patternUnits="userSpaceOnUse" patternTransform="rotate(45)"><rect width="8" height="8" fill="#fff59d"/><line x1="0" y1="0" x2="0" y2="8" stroke="#9e9e9e" stroke-width="3"/></pattern></defs>"##);
+    let _ = writeln!(svg, r##"<rect width="{}" height="{}" fill="#ffffff"/>"##, width, height);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" font-size="18" font-weight="bold">Too Many Types 2 type chart</text>"#, MARGIN, MARGIN + 20);
+    let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="end" font-style="italic">{}</text>"#, grid_x - MARGIN / 2, grid_y - MARGIN / 2, corner);
+
+    for (index, type_name) in type_names.iter().enumerate() {
+        let type_name = escape_xml(type_name);
+        // Column headers are rotated to fit long type names in narrow columns
+        let column_x = grid_x + index * CELL_SIZE + CELL_SIZE / 2;
+        let _ = writeln!(svg, r#"<text transform="translate({} {}) rotate(-90)" dominant-baseline="central">{}</text>"#, column_x, grid_y - MARGIN / 2, type_name);
+        let row_y = grid_y + index * CELL_SIZE + CELL_SIZE / 2;
+        let _ = writeln!(svg, r#"<text x="{}" y="{}" text-anchor="end" dominant-baseline="central">{}</text>"#, grid_x - MARGIN / 2, row_y, type_name);
+    }
+
+    for (row, attacking_type) in type_names.iter().enumerate() {
+        for (column, defending_type) in type_names.iter().enumerate() {
+            let effectiveness = type_chart.get_effectiveness(attacking_type, defending_type)?;
+            write_cell(&mut svg, grid_x + column * CELL_SIZE, grid_y + row * CELL_SIZE, &effectiveness);
+        }
+    }
+
+    let legend = [
+        (Effectiveness::SUPER_EFFECTIVE, "Super Effective"),
+        (Effectiveness::NOT_VERY_EFFECTIVE, "Not Very Effective"),
+        (Effectiveness::IMMUNE, "Immune"),
+        (Effectiveness::NEUTRAL, "Neutral"),
+        (Effectiveness::Unknown, "Unknown"),
+        (Effectiveness::Suspected(Multiplier::SUPER_EFFECTIVE), "Suspected"),
+    ];
+    let legend_y = grid_y + grid_size + MARGIN * 2;
+    for (index, (effectiveness, label)) in legend.iter().enumerate() {
+        let legend_x = MARGIN + index * LEGEND_ENTRY_WIDTH;
+        write_cell(&mut svg, legend_x, legend_y, effectiveness);
+        let _ = writeln!(svg, r#"<text x="{}" y="{}" dominant-baseline="central">{}</text>"#, legend_x + CELL_SIZE + MARGIN / 2, legend_y + CELL_SIZE / 2, label);
+    }
+    svg.push_str("</svg>\n");
+    return Ok(svg);
+}
+
+/// Saves the type chart drawn by [`type_chart_to_svg`] in an SVG file
+pub fn save_type_chart_svg(type_chart: &TypeChart, type_filter: &[String], filepath: &String) -> Result<(), TrackerError> {
+    let svg = type_chart_to_svg(type_chart, type_filter)?;
+    std::fs::write(filepath, svg)?;
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::{type_chart_with, TestDirectory};
+
+    fn svg_type_chart() -> TypeChart {
+        let mut type_chart = type_chart_with(&["Fire", "Grass", "Ghost"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.add_effectiveness(&"Grass".to_string(), &"Fire".to_string(), Effectiveness::NOT_VERY_EFFECTIVE).unwrap();
+        type_chart.add_effectiveness(&"Ghost".to_string(), &"Fire".to_string(), Effectiveness::IMMUNE).unwrap();
+        return type_chart;
+    }
+
+    #[test]
+    fn every_matchup_is_drawn_with_its_multiplier() {
+        let svg = type_chart_to_svg(&svg_type_chart(), &[]).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        for symbol in [">2</text>", ">½</text>", ">0</text>", ">Ghost</text>"] {
+            assert!(svg.contains(symbol), "{} is missing", symbol);
+        }
+        // The 6 unknown matchups, and the legend
+        assert_eq!(svg.matches(">?</text>").count(), 7);
+    }
+
+    #[test]
+    fn only_the_filtered_types_are_drawn() {
+        let type_chart = svg_type_chart();
+        let svg = type_chart_to_svg(&type_chart, &["Fire".to_string(), "Grass".to_string()]).unwrap();
+        assert!(!svg.contains(">Ghost</text>"));
+        assert_eq!(svg.matches(">?</text>").count(), 3);
+        assert!(matches!(type_chart_to_svg(&type_chart, &["Dragon".to_string()]), Err(TrackerError::UnknownType(_))));
+        assert_eq!(escape_xml("<R&D>"), "&lt;R&amp;D&gt;");
+    }
+
+    #[test]
+    fn the_saved_file_holds_the_drawing() {
+        let test_directory = TestDirectory::new("svg_export");
+        let filepath = test_directory.filepath("chart.svg");
+        let type_chart = svg_type_chart();
+        save_type_chart_svg(&type_chart, &[], &filepath).unwrap();
+        assert_eq!(std::fs::read_to_string(&filepath).unwrap(), type_chart_to_svg(&type_chart, &[]).unwrap());
+    }
+}
@@ -0,0 +1,135 @@
+use std::{cmp::Reverse, path::{Path, PathBuf}};
+
+use chrono::{Local, NaiveDateTime};
+
+use crate::{error::TrackerError, files::{files::get_types_from_file, species_file::{read_species_file, species_filepath}}, type_chart::TypeChart};
+
+/// Number of backups kept for each chart file, the oldest ones are deleted first
+pub const MAX_BACKUPS: usize = 10;
+
+// Backup names sort in chronological order
+const BACKUP_NAME_FORMAT: &str = "%Y-%m-%d_%H-%M-%S%.3f";
+
+/// Previous version of a chart file, saved just before it was replaced
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Backup {
+    /// Path of the backup file
+    pub filepath: String,
+    /// Local time the backup was made at
+    pub saved_at: NaiveDateTime,
+}
+
+/// Directory the backups of a chart file are kept in, next to the chart file
+pub fn backups_directory(filepath: &String) -> PathBuf {
+    let mut directory_name = Path::new(filepath).file_name().unwrap_or_default().to_os_string();
+    directory_name.push(".backups");
+    return Path::new(filepath).with_file_name(directory_name);
+}
+
+/// Lists the backups of a chart file from the most recent to the oldest
+pub fn list_backups(filepath: &String) -> Result<Vec<Backup>, TrackerError> {
+    let directory = backups_directory(filepath);
+    if !directory.is_dir() {
+        return Ok(Vec::new());
+    }
+    let mut backups = Vec::new();
+    for entry in std::fs::read_dir(&directory)? {
+        let path = entry?.path();
+        let backup_name = path.file_stem().and_then(|backup_name| backup_name.to_str()).unwrap_or_default();
+        // Files the tracker didn't write are left alone
+        if let Ok(saved_at) = NaiveDateTime::parse_from_str(backup_name, BACKUP_NAME_FORMAT) {
+            backups.push(Backup { filepath: path.to_string_lossy().to_string(), saved_at });
+        }
+    }
+    backups.sort_by_key(|backup| Reverse(backup.saved_at));
+    return Ok(backups);
+}
+
+/// Loads the type chart saved in a backup with the species it had
+///
+/// Backups made before the species were backed up keep the current species of the chart file instead
+pub fn read_backup(filepath: &String, backup: &Backup) -> Result<TypeChart, TrackerError> {
+    let mut type_chart = get_types_from_file(&backup.filepath)?;
+    if !std::fs::exists(species_filepath(&backup.filepath))? {
+        type_chart.species = read_species_file(filepath)?;
+    }
+    return Ok(type_chart);
+}
+
+// Copies the current chart file and its species in the backups directory, and deletes the oldest backups past MAX_BACKUPS
+pub(crate) fn back_up_file(filepath: &String) -> Result<(), TrackerError> {
+    let directory = backups_directory(filepath);
+    std::fs::create_dir_all(&directory)?;
+    let backup_name = format!("{}.json", Local::now().format(BACKUP_NAME_FORMAT));
+    let backup_filepath = directory.join(backup_name).to_string_lossy().to_string();
+    std::fs::copy(filepath, &backup_filepath)?;
+    // Written even without species, so that restoring the backup gives back an empty species list
+    let mut species_content = serde_json::to_string_pretty(&read_species_file(filepath)?)?;
+    species_content.push('\n');
+    std::fs::write(species_filepath(&backup_filepath), species_content)?;
+    for old_backup in list_backups(filepath)?.iter().skip(MAX_BACKUPS) {
+        std::fs::remove_file(&old_backup.filepath)?;
+        let old_species_filepath = species_filepath(&old_backup.filepath);
+        if std::fs::exists(&old_species_filepath)? {
+            std::fs::remove_file(old_species_filepath)?;
+        }
+    }
+    return Ok(());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{files::save_types_to_file, test_helpers::TestDirectory};
+
+    // Saves a chart with Treecko, then adds a type so that the first version is backed up
+    fn save_two_versions(filepath: &String) -> TypeChart {
+        let mut type_chart = TypeChart::empty();
+        type_chart.add_new_type(&"Grass".to_string()).unwrap();
+        type_chart.set_species(&"Treecko".to_string(), &["Grass".to_string()]).unwrap();
+        save_types_to_file(&type_chart, filepath).unwrap();
+        type_chart.add_new_type(&"Water".to_string()).unwrap();
+        save_types_to_file(&type_chart, filepath).unwrap();
+        return type_chart;
+    }
+
+    #[test]
+    fn restoring_a_backup_keeps_its_species() {
+        let test_directory = TestDirectory::new("backups_keeps_species");
+        let filepath = test_directory.filepath("chart.json");
+        save_two_versions(&filepath);
+        let backups = list_backups(&filepath).unwrap();
+        assert_eq!(backups.len(), 1);
+
+        let restored_type_chart = read_backup(&filepath, &backups[0]).unwrap();
+        assert_eq!(restored_type_chart.get_type_list(), vec!["Grass".to_string()]);
+        assert_eq!(restored_type_chart.get_species_types("Treecko").unwrap(), vec!["Grass".to_string()]);
+
+        save_types_to_file(&restored_type_chart, &filepath).unwrap();
+        let saved_type_chart = get_types_from_file(&filepath).unwrap();
+        assert_eq!(saved_type_chart.get_species_types("Treecko").unwrap(), vec!["Grass".to_string()]);
+    }
+
+    #[test]
+    fn backups_without_species_keep_the_current_species() {
+        let test_directory = TestDirectory::new("backups_old_backup");
+        let filepath = test_directory.filepath("chart.json");
+        save_two_versions(&filepath);
+        let backups = list_backups(&filepath).unwrap();
+        // Backups made before the species were backed up only have the chart file
+        std::fs::remove_file(species_filepath(&backups[0].filepath)).unwrap();
+
+        let restored_type_chart = read_backup(&filepath, &backups[0]).unwrap();
+        assert_eq!(restored_type_chart.get_species_types("Treecko").unwrap(), vec!["Grass".to_string()]);
+    }
+
+    #[test]
+    fn species_backups_are_not_listed_as_backups() {
+        let test_directory = TestDirectory::new("backups_not_listed");
+        let filepath = test_directory.filepath("chart.json");
+        save_two_versions(&filepath);
+        let backups = list_backups(&filepath).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert!(std::fs::exists(species_filepath(&backups[0].filepath)).unwrap());
+    }
+}
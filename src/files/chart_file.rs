@@ -0,0 +1,127 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::TrackerError, type_chart::{Effectiveness, JournalEntry, Observation, TypeChart, TypeMap}};
+
+/// Version of the chart file format written by this tracker
+pub const FORMAT_VERSION: u32 = 1;
+
+// Everything is saved with names rather than positions so the file can be read and edited by hand
+#[derive(Serialize, Deserialize, Debug)]
+pub(crate) struct ChartFile {
+    format_version: u32,
+    #[serde(default)]
+    metadata: BTreeMap<String, String>,
+    types: Vec<String>,
+    // Attacking type, then defending type, unknown matchups are left out
+    #[serde(default)]
+    matchups: BTreeMap<String, BTreeMap<String, Effectiveness>>,
+    // Kept in the file so changes can be undone after the tracker was closed
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    history: Vec<JournalEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    undone_history: Vec<JournalEntry>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    observations: Vec<Observation>,
+}
+
+// Only used to check the version before reading the rest of the file, since its layout may have changed
+#[derive(Deserialize)]
+struct ChartFileVersion {
+    format_version: u32,
+}
+
+impl ChartFile {
+    pub(crate) fn from_type_chart(type_chart: &TypeChart) -> ChartFile {
+        let mut matchups = BTreeMap::new();
+        for (type_name, effectiveness_map) in &type_chart.type_map {
+            let known_matchups: BTreeMap<String, Effectiveness> = effectiveness_map.iter()
+                .filter(|(_, effectiveness)| **effectiveness != Effectiveness::Unknown)
+                .map(|(opposing_type, effectiveness)| (opposing_type.clone(), *effectiveness))
+                .collect();
+            if !known_matchups.is_empty() {
+                matchups.insert(type_name.clone(), known_matchups);
+            }
+        }
+        return ChartFile {
+            format_version: FORMAT_VERSION,
+            metadata: type_chart.metadata.clone(),
+            types: type_chart.type_list.clone(),
+            matchups,
+            history: type_chart.history.clone(),
+            undone_history: type_chart.undone_history.clone(),
+            observations: type_chart.observations.clone(),
+        };
+    }
+
+    pub(crate) fn from_json(content: &str) -> Result<ChartFile, TrackerError> {
+        let version: ChartFileVersion = serde_json::from_str(content)?;
+        if version.format_version > FORMAT_VERSION {
+            return Err(TrackerError::UnsupportedFormatVersion(version.format_version));
+        }
+        return Ok(serde_json::from_str(content)?);
+    }
+
+    pub(crate) fn into_type_chart(self) -> Result<TypeChart, TrackerError> {
+        let mut type_map: TypeMap = HashMap::new();
+        for type_name in &self.types {
+            if type_map.insert(type_name.clone(), HashMap::new()).is_some() {
+                return Err(TrackerError::DuplicateType(type_name.clone()));
+            }
+        }
+        for (type_name, known_matchups) in self.matchups {
+            let effectiveness_map = match type_map.get_mut(&type_name) {
+                None => return Err(TrackerError::UnknownType(type_name)),
+                Some(effectiveness_map) => effectiveness_map,
+            };
+            for (opposing_type, effectiveness) in known_matchups {
+                if !self.types.contains(&opposing_type) {
+                    return Err(TrackerError::UnknownType(opposing_type));
+                }
+                effectiveness_map.insert(opposing_type, effectiveness);
+            }
+        }
+        let mut type_chart = TypeChart::new(type_map, self.types);
+        type_chart.metadata = self.metadata;
+        type_chart.history = self.history;
+        type_chart.undone_history = self.undone_history;
+        type_chart.observations = self.observations;
+        return Ok(type_chart);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::type_chart_with, type_chart::Multiplier};
+
+    #[test]
+    fn a_chart_file_gives_back_the_type_chart_it_was_made_from() {
+        let mut type_chart = type_chart_with(&["Fire", "Water"]);
+        type_chart.set_metadata(&"game".to_string(), &"Emerald".to_string());
+        type_chart.add_effectiveness(&"Water".to_string(), &"Fire".to_string(), Effectiveness::Known(Multiplier::SUPER_EFFECTIVE)).unwrap();
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Water".to_string(), Effectiveness::Known(Multiplier::NOT_VERY_EFFECTIVE)).unwrap();
+
+        let content = serde_json::to_string(&ChartFile::from_type_chart(&type_chart)).unwrap();
+        let read_type_chart = ChartFile::from_json(&content).unwrap().into_type_chart().unwrap();
+        assert_eq!(read_type_chart.get_type_list(), type_chart.get_type_list());
+        assert_eq!(read_type_chart.get_type_map(), type_chart.get_type_map());
+        assert_eq!(read_type_chart.get_metadata(), type_chart.get_metadata());
+    }
+
+    #[test]
+    fn newer_format_versions_are_refused() {
+        let content = format!("{{\"format_version\": {}, \"types\": []}}", FORMAT_VERSION + 1);
+        assert!(matches!(ChartFile::from_json(&content), Err(TrackerError::UnsupportedFormatVersion(version)) if version == FORMAT_VERSION + 1));
+        // Older files may lack the fields added since then
+        let content = format!("{{\"format_version\": {}, \"types\": [\"Fire\"]}}", FORMAT_VERSION);
+        assert_eq!(ChartFile::from_json(&content).unwrap().into_type_chart().unwrap().get_type_list(), vec!["Fire".to_string()]);
+    }
+
+    #[test]
+    fn matchups_of_types_not_in_the_list_are_refused() {
+        let content = "{\"format_version\": 1, \"types\": [\"Fire\"], \"matchups\": {\"Fire\": {\"Water\": \"Neutral\"}}}";
+        assert!(matches!(ChartFile::from_json(content).unwrap().into_type_chart(), Err(TrackerError::UnknownType(type_name)) if type_name == "Water"));
+    }
+}
@@ -0,0 +1,229 @@
+use std::{collections::HashMap, fmt::Display};
+
+use crate::{error::TrackerError, type_chart::{Effectiveness, TypeChart, TypeMap}};
+
+/// Something an importer had to fix or ignore, with the position it was found at (1-based)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportRepair {
+    /// Line of the problem
+    pub line: usize,
+    /// Column of the problem, missing when it is about the whole line
+    pub column: Option<usize>,
+    /// What was fixed or ignored
+    pub description: String,
+}
+
+impl Display for ImportRepair {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.column {
+            None => return write!(f, "line {}: {}", self.line, self.description),
+            Some(column) => return write!(f, "line {}, column {}: {}", self.line, column, self.description),
+        }
+    }
+}
+
+/// Everything an importer had to repair, empty if the file was imported as is
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ImportReport {
+    /// Repairs in the order they were made
+    pub repairs: Vec<ImportRepair>,
+}
+
+impl ImportReport {
+    pub(crate) fn repair(&mut self, line: usize, column: Option<usize>, description: String) {
+        self.repairs.push(ImportRepair { line, column, description });
+    }
+}
+
+// Finds the type a name written in the file refers to, ignoring the case if there is no exact match
+fn find_type_name(type_list: &[String], type_name: &str) -> Option<String> {
+    if let Some(found_type) = type_list.iter().find(|current_type| *current_type == type_name) {
+        return Some(found_type.clone());
+    }
+    let lowercase_name = type_name.to_lowercase();
+    return type_list.iter().find(|current_type| current_type.to_lowercase() == lowercase_name).cloned();
+}
+
+// The position of a record starts at the end of the previous one, before any blank line the reader skipped
+fn record_line(content: &str, position: &csv::Position) -> usize {
+    let skipped_lines = content[position.byte() as usize..].chars()
+        .take_while(|character| *character == '\n' || *character == '\r')
+        .filter(|character| *character == '\n')
+        .count();
+    return position.line() as usize + skipped_lines;
+}
+
+/// Reads a type chart from a labelled CSV matrix, like one written by hand or exported from a spreadsheet
+///
+/// The first line lists the defending types after a corner cell, then each line starts with an attacking type
+/// followed by its effectiveness against every defending type. Rows and columns can be in any order,
+/// missing ones are filled as unknown, and effectivenesses can be abbreviated like `SE`, `NVE`, `0.5x` or `2`
+/// (see [`Effectiveness::parse_lenient`]). Anything that had to be fixed or ignored is listed in the report
+pub fn import_labelled_csv(filepath: &String) -> Result<(TypeChart, ImportReport), TrackerError> {
+    let content = std::fs::read_to_string(filepath)?;
+    let mut reader_builder = csv::ReaderBuilder::new();
+    // Rows of different lengths are repaired instead of rejected
+    let mut file_reader = reader_builder.has_headers(false).flexible(true).from_reader(content.as_bytes());
+    let mut report = ImportReport::default();
+    let mut type_list: Vec<String> = Vec::new();
+    // Defending type of every column of the file, None for the columns that were ignored
+    let mut column_types: Vec<Option<String>> = Vec::new();
+    let mut row_lines: HashMap<String, usize> = HashMap::new();
+    let mut hashmap: TypeMap = HashMap::new();
+    let mut header_read = false;
+
+    for (index, record) in file_reader.records().enumerate() {
+        let record = match record {
+            Err(err) => {
+                let line = err.position().map_or(index + 1, |position| position.line() as usize);
+                return Err(TrackerError::MalformedRow { line, column: None, reason: err.to_string() });
+            },
+            Ok(record) => record,
+        };
+        let line = record.position().map_or(index + 1, |position| record_line(&content, position));
+        if record.iter().all(|cell| cell.trim().is_empty()) {
+            report.repair(line, None, "Empty line ignored".to_string());
+            continue;
+        }
+
+        if !header_read {
+            header_read = true;
+            // The first cell is the corner of the matrix, it doesn't name a type
+            for (column, type_name) in record.iter().enumerate().skip(1) {
+                let type_name = type_name.trim();
+                if type_name.is_empty() {
+                    report.repair(line, Some(column + 1), "Column without a type name ignored".to_string());
+                    column_types.push(None);
+                } else if let Some(existing_type) = find_type_name(&type_list, type_name) {
+                    report.repair(line, Some(column + 1), format!("Type {} already has a column, this one was ignored", existing_type));
+                    column_types.push(None);
+                } else {
+                    type_list.push(type_name.to_string());
+                    column_types.push(Some(type_name.to_string()));
+                }
+            }
+            continue;
+        }
+
+        let row_label = record.get(0).unwrap_or_default().trim();
+        if row_label.is_empty() {
+            report.repair(line, Some(1), "Row without a type name ignored".to_string());
+            continue;
+        }
+        let attacking_type = match find_type_name(&type_list, row_label) {
+            None => {
+                report.repair(line, Some(1), format!("Type {} has no column, its matchups when defending are unknown", row_label));
+                type_list.push(row_label.to_string());
+                row_label.to_string()
+            },
+            Some(attacking_type) => {
+                if attacking_type != row_label {
+                    report.repair(line, Some(1), format!("{} was read as the type {}", row_label, attacking_type));
+                }
+                attacking_type
+            },
+        };
+        if let Some(first_line) = row_lines.get(&attacking_type) {
+            report.repair(line, Some(1), format!("Type {} already has a row on line {}, this one was ignored", attacking_type, first_line));
+            continue;
+        }
+        row_lines.insert(attacking_type.clone(), line);
+
+        let type_matchups = hashmap.entry(attacking_type.clone()).or_default();
+        for (column, cell) in record.iter().enumerate().skip(1) {
+            let defending_type = match column_types.get(column - 1) {
+                None => {
+                    report.repair(line, Some(column + 1), format!("Extra cell {:?} ignored, there is no type for this column", cell.trim()));
+                    continue;
+                },
+                Some(None) => continue,
+                Some(Some(defending_type)) => defending_type,
+            };
+            // Empty cells are how unknown matchups are usually written by hand
+            if cell.trim().is_empty() {
+                continue;
+            }
+            match Effectiveness::parse_lenient(cell) {
+                Err(_) => {
+                    report.repair(line, Some(column + 1), format!("{:?} is not an effectiveness, {} against {} was left unknown", cell.trim(), attacking_type, defending_type));
+                },
+                Ok(effectiveness) => {
+                    type_matchups.insert(defending_type.clone(), effectiveness);
+                },
+            }
+        }
+        let missing_cells = column_types.iter().skip(record.len().saturating_sub(1)).filter(|column_type| column_type.is_some()).count();
+        if missing_cells > 0 {
+            report.repair(line, None, format!("Row of {} is missing {} cells, they were left unknown", attacking_type, missing_cells));
+        }
+    }
+
+    for type_name in &type_list {
+        if !row_lines.contains_key(type_name) {
+            report.repair(1, None, format!("Type {} has no row, its matchups when attacking are unknown", type_name));
+        }
+    }
+    return Ok((TypeChart::new(hashmap, type_list), report));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::TestDirectory, type_chart::Multiplier};
+
+    fn effectiveness(type_chart: &TypeChart, attacking_type: &str, defending_type: &str) -> Effectiveness {
+        return type_chart.get_effectiveness(&attacking_type.to_string(), &defending_type.to_string()).unwrap();
+    }
+
+    #[test]
+    fn rows_and_columns_can_be_in_any_order_with_abbreviations() {
+        let test_directory = TestDirectory::new("csv_import_order");
+        let filepath = test_directory.write_file("chart.csv", "\
+Attacking,Water,Fire,Grass
+Grass,SE,nve,1/2
+Fire,0.5x,½,super
+Water,n,2,imm?
+");
+        let (type_chart, report) = import_labelled_csv(&filepath).unwrap();
+        assert!(report.repairs.is_empty(), "{:?}", report.repairs);
+        assert_eq!(type_chart.get_type_list(), ["Water", "Fire", "Grass"].map(String::from));
+        assert_eq!(effectiveness(&type_chart, "Grass", "Water"), Effectiveness::SUPER_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Grass", "Grass"), Effectiveness::NOT_VERY_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Fire", "Water"), Effectiveness::NOT_VERY_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Fire", "Grass"), Effectiveness::SUPER_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Water", "Water"), Effectiveness::NEUTRAL);
+        assert_eq!(effectiveness(&type_chart, "Water", "Grass"), Effectiveness::Suspected(Multiplier::IMMUNE));
+    }
+
+    #[test]
+    fn missing_and_wrong_cells_are_repaired_and_reported() {
+        let test_directory = TestDirectory::new("csv_import_repairs");
+        let filepath = test_directory.write_file("chart.csv", "\
+,Fire,Water,Grass
+fire,nve,NVE
+,,
+Dragon,2,,,SE
+Fire,SE,SE,SE
+Water,Normal,,
+");
+        let (type_chart, report) = import_labelled_csv(&filepath).unwrap();
+        assert_eq!(type_chart.get_type_list(), ["Fire", "Water", "Grass", "Dragon"].map(String::from));
+        let repairs: Vec<String> = report.repairs.iter().map(|repair| repair.to_string()).collect();
+        assert_eq!(repairs, vec![
+            "line 2, column 1: fire was read as the type Fire",
+            "line 2: Row of Fire is missing 1 cells, they were left unknown",
+            "line 3: Empty line ignored",
+            "line 4, column 1: Type Dragon has no column, its matchups when defending are unknown",
+            "line 4, column 5: Extra cell \"SE\" ignored, there is no type for this column",
+            "line 5, column 1: Type Fire already has a row on line 2, this one was ignored",
+            "line 6, column 2: \"Normal\" is not an effectiveness, Water against Fire was left unknown",
+            "line 1: Type Grass has no row, its matchups when attacking are unknown",
+        ]);
+        // The first row of a type is kept, and "normal" names a type, not a neutral matchup
+        assert_eq!(effectiveness(&type_chart, "Fire", "Fire"), Effectiveness::NOT_VERY_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Fire", "Grass"), Effectiveness::Unknown);
+        assert_eq!(effectiveness(&type_chart, "Dragon", "Fire"), Effectiveness::SUPER_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Water", "Fire"), Effectiveness::Unknown);
+        assert_eq!(effectiveness(&type_chart, "Grass", "Fire"), Effectiveness::Unknown);
+    }
+}
@@ -0,0 +1,140 @@
+use std::{collections::{HashMap, HashSet}, io::Write, path::{Path, PathBuf}};
+
+use crate::{error::TrackerError, type_chart::{Discovery, TypeChart}};
+
+/// File the discovery log of a chart file is kept in, next to the chart file
+///
+/// The log is only ever appended to, with one JSON object per line
+pub fn discovery_log_filepath(filepath: &String) -> PathBuf {
+    let mut log_name = Path::new(filepath).file_name().unwrap_or_default().to_os_string();
+    log_name.push(".discoveries.jsonl");
+    return Path::new(filepath).with_file_name(log_name);
+}
+
+/// Reads every discovery logged for a chart file, from the oldest to the most recent
+pub fn read_discovery_log(filepath: &String) -> Result<Vec<Discovery>, TrackerError> {
+    let log_filepath = discovery_log_filepath(filepath);
+    if !std::fs::exists(&log_filepath)? {
+        return Ok(Vec::new());
+    }
+    let mut discoveries = Vec::new();
+    for (index, line) in std::fs::read_to_string(&log_filepath)?.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        match serde_json::from_str(line) {
+            Err(err) => return Err(TrackerError::MalformedRow { line: index + 1, column: Some(err.column()), reason: err.to_string() }),
+            Ok(discovery) => discoveries.push(discovery),
+        }
+    }
+    return Ok(discoveries);
+}
+
+// Adds the discoveries of the type chart that aren't logged yet, a type chart can be saved several times
+pub(crate) fn append_to_discovery_log(filepath: &String, discoveries: &[Discovery]) -> Result<(), TrackerError> {
+    if discoveries.is_empty() {
+        return Ok(());
+    }
+    let logged_discoveries: HashSet<Discovery> = read_discovery_log(filepath)?.into_iter().collect();
+    let mut content = String::new();
+    for discovery in discoveries.iter().filter(|discovery| !logged_discoveries.contains(discovery)) {
+        content.push_str(&serde_json::to_string(discovery)?);
+        content.push('\n');
+    }
+    if content.is_empty() {
+        return Ok(());
+    }
+    let mut log_file = std::fs::OpenOptions::new().create(true).append(true).open(discovery_log_filepath(filepath))?;
+    log_file.write_all(content.as_bytes())?;
+    return Ok(());
+}
+
+/// Rebuilds the matchups of `type_list` by replaying the discoveries in order, every matchup starts as unknown
+///
+/// Discoveries about types missing from `type_list` are skipped and returned with the type chart
+pub fn replay_discoveries(type_list: &[String], discoveries: &[Discovery]) -> (TypeChart, Vec<Discovery>) {
+    let mut type_chart = TypeChart::new(HashMap::new(), type_list.to_vec());
+    let mut skipped_discoveries = Vec::new();
+    for discovery in discoveries {
+        if !type_list.contains(&discovery.attacking_type) || !type_list.contains(&discovery.defending_type) {
+            skipped_discoveries.push(discovery.clone());
+            continue;
+        }
+        // Replaying isn't a change of the type chart, it shouldn't end up in its history or in the log again
+        type_chart.set_matchup(&discovery.attacking_type, &discovery.defending_type, discovery.new);
+    }
+    return (type_chart, skipped_discoveries);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::{type_chart_with, TestDirectory}, type_chart::Effectiveness};
+
+    #[test]
+    fn renamed_types_are_replayed_under_their_new_name() {
+        let mut type_chart = type_chart_with(&["Fier", "Grass"]);
+        type_chart.add_effectiveness(&"Fier".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.add_effectiveness(&"Grass".to_string(), &"Fier".to_string(), Effectiveness::NOT_VERY_EFFECTIVE).unwrap();
+        type_chart.rename_type(&"Fier".to_string(), &"Fire".to_string()).unwrap();
+
+        let discoveries = type_chart.get_discoveries();
+        assert!(discoveries.iter().any(|discovery| discovery.attacking_type == "Fire" && discovery.defending_type == "Grass"));
+        let (replayed_type_chart, skipped_discoveries) = replay_discoveries(&type_chart.get_type_list(), &discoveries);
+        assert_eq!(replayed_type_chart.get_type_map(), type_chart.get_type_map());
+        // The discoveries made before the rename are about a type that doesn't exist anymore
+        assert_eq!(skipped_discoveries.len(), 2);
+    }
+
+    #[test]
+    fn undoing_a_rename_logs_the_matchups_under_the_previous_name() {
+        let mut type_chart = type_chart_with(&["Fier", "Grass"]);
+        type_chart.add_effectiveness(&"Fier".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.rename_type(&"Fier".to_string(), &"Fire".to_string()).unwrap();
+        type_chart.undo();
+
+        let last_discovery = type_chart.get_discoveries().pop().unwrap();
+        assert_eq!((last_discovery.attacking_type.as_str(), last_discovery.new), ("Fier", Effectiveness::SUPER_EFFECTIVE));
+        let (replayed_type_chart, _) = replay_discoveries(&type_chart.get_type_list(), &type_chart.get_discoveries());
+        assert_eq!(replayed_type_chart.get_type_map(), type_chart.get_type_map());
+    }
+
+    #[test]
+    fn undoing_a_removal_logs_the_matchups_put_back() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.add_effectiveness(&"Grass".to_string(), &"Fire".to_string(), Effectiveness::NOT_VERY_EFFECTIVE).unwrap();
+        type_chart.remove_existing_type(&"Grass".to_string()).unwrap();
+        type_chart.undo();
+
+        let (replayed_type_chart, skipped_discoveries) = replay_discoveries(&type_chart.get_type_list(), &type_chart.get_discoveries());
+        assert_eq!(replayed_type_chart.get_type_map(), type_chart.get_type_map());
+        assert!(skipped_discoveries.is_empty());
+    }
+
+    #[test]
+    fn undoing_a_merge_logs_the_matchups_of_the_merged_type() {
+        let mut type_chart = type_chart_with(&["Fire", "Fier", "Grass"]);
+        type_chart.add_effectiveness(&"Fier".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.add_effectiveness(&"Grass".to_string(), &"Fier".to_string(), Effectiveness::NOT_VERY_EFFECTIVE).unwrap();
+        type_chart.merge_types(&"Fire".to_string(), &"Fier".to_string(), &[]).unwrap();
+        type_chart.undo();
+
+        let (replayed_type_chart, _) = replay_discoveries(&type_chart.get_type_list(), &type_chart.get_discoveries());
+        assert_eq!(replayed_type_chart.get_type_map(), type_chart.get_type_map());
+        assert_eq!(type_chart.get_effectiveness(&"Fier".to_string(), &"Grass".to_string()).unwrap(), Effectiveness::SUPER_EFFECTIVE);
+    }
+
+    #[test]
+    fn saving_twice_logs_each_discovery_once() {
+        let test_directory = TestDirectory::new("discovery_log");
+        let filepath = test_directory.filepath("chart.json");
+        let mut type_chart = type_chart_with(&["Fire", "Grass"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        append_to_discovery_log(&filepath, &type_chart.discoveries).unwrap();
+        type_chart.add_effectiveness(&"Grass".to_string(), &"Fire".to_string(), Effectiveness::NOT_VERY_EFFECTIVE).unwrap();
+        append_to_discovery_log(&filepath, &type_chart.discoveries).unwrap();
+
+        assert_eq!(read_discovery_log(&filepath).unwrap(), type_chart.get_discoveries());
+    }
+}
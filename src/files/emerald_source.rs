@@ -0,0 +1,651 @@
+use std::{collections::{BTreeMap, HashMap}, path::Path};
+
+use crate::{error::TrackerError, files::{ImportReport, files::write_synced}, type_chart::{Effectiveness, Multiplier, TypeChart, TypeMap}};
+
+// Constants named like types that no Pokemon can have
+const NON_TYPE_CONSTANTS: [&str; 4] = ["TYPE_NONE", "TYPE_MYSTERY", "TYPE_FORESIGHT", "TYPE_ENDTABLE"];
+
+// Array of the decomp listing every matchup that isn't neutral
+const TABLE_NAME: &str = "gTypeEffectiveness";
+
+// A species of the source file, with the line it starts on
+struct SpeciesEntry {
+    line: usize,
+    constant: String,
+    type_constants: Vec<String>,
+}
+
+fn is_type_constant(identifier: &str) -> bool {
+    return identifier.starts_with("TYPE_") && !identifier.starts_with("TYPE_MUL_") && !NON_TYPE_CONSTANTS.contains(&identifier);
+}
+
+fn is_identifier_character(character: char) -> bool {
+    return character.is_ascii_alphanumeric() || character == '_';
+}
+
+// Replaces the comments by spaces, keeping the line breaks so that positions still give the right line
+fn strip_comments(source: &str) -> String {
+    let mut stripped = String::with_capacity(source.len());
+    let mut characters = source.chars().peekable();
+    let mut in_string = false;
+    while let Some(character) = characters.next() {
+        if in_string {
+            stripped.push(character);
+            if character == '\\' {
+                stripped.extend(characters.next());
+            } else if character == '"' {
+                in_string = false;
+            }
+            continue;
+        }
+        match (character, characters.peek()) {
+            ('/', Some('/')) => {
+                while characters.next_if(|next_character| *next_character != '\n').is_some() {
+                    stripped.push(' ');
+                }
+                stripped.push(' ');
+            },
+            ('/', Some('*')) => {
+                characters.next();
+                stripped.push_str("  ");
+                let mut previous_character = ' ';
+                for comment_character in characters.by_ref() {
+                    stripped.push(if comment_character == '\n' { '\n' } else { ' ' });
+                    if previous_character == '*' && comment_character == '/' {
+                        break;
+                    }
+                    previous_character = comment_character;
+                }
+            },
+            _ => {
+                in_string = character == '"';
+                stripped.push(character);
+            },
+        }
+    }
+    return stripped;
+}
+
+// Every identifier of the source with its byte position
+fn identifiers(source: &str) -> Vec<(usize, &str)> {
+    let mut found_identifiers = Vec::new();
+    let mut start = None;
+    for (position, character) in source.char_indices().chain(std::iter::once((source.len(), ' '))) {
+        match (start, is_identifier_character(character)) {
+            (None, true) => start = Some(position),
+            (Some(identifier_start), false) => {
+                // Numbers aren't identifiers
+                if !source[identifier_start..].starts_with(|first_character: char| first_character.is_ascii_digit()) {
+                    found_identifiers.push((identifier_start, &source[identifier_start..position]));
+                }
+                start = None;
+            },
+            _ => (),
+        }
+    }
+    return found_identifiers;
+}
+
+fn line_at(source: &str, position: usize) -> usize {
+    return source[..position].matches('\n').count() + 1;
+}
+
+// "MR_MIME" gives "Mr Mime"
+fn title_case(words: &str) -> String {
+    let words: Vec<String> = words.split('_')
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            let mut characters = word.chars();
+            let first_character = characters.next().map(|character| character.to_ascii_uppercase()).unwrap_or_default();
+            return std::iter::once(first_character).chain(characters.map(|character| character.to_ascii_lowercase())).collect::<String>();
+        })
+        .collect();
+    return words.join(" ");
+}
+
+// Only the letters and digits are compared, so "TYPE_SOUND_WAVE" finds the type "Sound-Wave"
+fn simplified_name(name: &str) -> String {
+    return name.chars().filter(char::is_ascii_alphanumeric).map(|character| character.to_ascii_lowercase()).collect();
+}
+
+/// Finds the type of the chart a `TYPE_*` constant of the decomp refers to,
+/// or names a new type after the constant if the chart doesn't have it (`TYPE_FIRE` gives `Fire`)
+pub fn type_name_from_constant(type_list: &[String], constant: &str) -> String {
+    let name = constant.strip_prefix("TYPE_").unwrap_or(constant);
+    let simplified_constant = simplified_name(name);
+    match type_list.iter().find(|type_name| simplified_name(type_name) == simplified_constant) {
+        None => return title_case(name),
+        Some(type_name) => return type_name.clone(),
+    }
+}
+
+// "Sound-Wave" gives "TYPE_SOUND_WAVE"
+fn type_constant(type_name: &str) -> String {
+    let name: String = type_name.trim().chars()
+        .map(|character| if character.is_ascii_alphanumeric() { character.to_ascii_uppercase() } else { '_' })
+        .collect();
+    return format!("TYPE_{}", name);
+}
+
+// Type constants in the order they are defined, with `#define TYPE_FIRE 10` or as members of an enum
+fn parse_type_definitions(source: &str) -> Vec<(usize, String)> {
+    let mut definitions: Vec<(usize, String)> = Vec::new();
+    let found_identifiers = identifiers(source);
+    let mut enum_depth: Option<usize> = None;
+    let mut depth: usize = 0;
+    let mut previous_end = 0;
+    for (index, (position, identifier)) in found_identifiers.iter().enumerate() {
+        let between = &source[previous_end..*position];
+        for character in between.chars() {
+            match character {
+                '{' => depth += 1,
+                '}' => {
+                    depth = depth.saturating_sub(1);
+                    if enum_depth.is_some_and(|enum_depth| depth < enum_depth) {
+                        enum_depth = None;
+                    }
+                },
+                _ => (),
+            }
+        }
+        previous_end = position + identifier.len();
+        if *identifier == "enum" {
+            enum_depth = Some(depth + 1);
+            continue;
+        }
+        let defined = index > 0 && found_identifiers[index - 1].1 == "define" && source[..*position].trim_end().ends_with("define");
+        let enum_member = enum_depth == Some(depth) && between.trim_end().ends_with(['{', ',']);
+        if identifier.starts_with("TYPE_") && (defined || enum_member) && !definitions.iter().any(|(_, constant)| constant == identifier) {
+            definitions.push((line_at(source, *position), identifier.to_string()));
+        }
+    }
+    return definitions;
+}
+
+// Type constants defined in a file like `include/constants/pokemon.h`, in their order
+fn read_type_constants(types_filepath: &String, report: &mut ImportReport) -> Result<Vec<String>, TrackerError> {
+    let source = strip_comments(&std::fs::read_to_string(types_filepath)?);
+    let mut type_constants = Vec::new();
+    for (line, constant) in parse_type_definitions(&source) {
+        if is_type_constant(&constant) {
+            type_constants.push(constant);
+        } else {
+            report.repair(line, None, format!("{} of {} isn't a type Pokemon can have, ignored", constant, types_filepath));
+        }
+    }
+    return Ok(type_constants);
+}
+
+// Text of a field after its =, up to the comma ending it or the brace closing the struct
+fn field_value(text: &str) -> &str {
+    let text = text.trim_start();
+    let text = text.strip_prefix('=').unwrap_or(text);
+    let mut depth = 0;
+    for (position, character) in text.char_indices() {
+        match character {
+            '{' | '(' => depth += 1,
+            '}' | ')' if depth == 0 => return &text[..position],
+            '}' | ')' => depth -= 1,
+            ',' | ';' if depth == 0 => return &text[..position],
+            _ => (),
+        }
+    }
+    return text;
+}
+
+// Species entries written `[SPECIES_X] = { ... }`, with the types of `.types = { TYPE_X, TYPE_Y }`
+// or of `.type1 = TYPE_X, .type2 = TYPE_Y` in older versions of the decomp
+fn parse_species_entries(source: &str) -> Vec<SpeciesEntry> {
+    let found_identifiers = identifiers(source);
+    let mut entry_starts = Vec::new();
+    for (position, identifier) in &found_identifiers {
+        let after = source[position + identifier.len()..].trim_start();
+        let is_entry = identifier.starts_with("SPECIES_")
+            && source[..*position].trim_end().ends_with('[')
+            && after.strip_prefix(']').is_some_and(|after| after.trim_start().starts_with('='));
+        if is_entry {
+            entry_starts.push((*position, identifier.to_string()));
+        }
+    }
+
+    let mut entries = Vec::new();
+    for (index, (start, constant)) in entry_starts.iter().enumerate() {
+        let end = entry_starts.get(index + 1).map_or(source.len(), |(next_start, _)| *next_start);
+        let body = &source[start + constant.len()..end];
+        let mut type_constants: Vec<String> = Vec::new();
+        for (position, identifier) in identifiers(body) {
+            if !["types", "type1", "type2"].contains(&identifier) || !body[..position].trim_end().ends_with('.') {
+                continue;
+            }
+            for (_, type_constant) in identifiers(field_value(&body[position + identifier.len()..])) {
+                if type_constant.starts_with("TYPE_") && !type_constants.iter().any(|current_constant| current_constant == type_constant) {
+                    type_constants.push(type_constant.to_string());
+                }
+            }
+        }
+        entries.push(SpeciesEntry { line: line_at(source, *start), constant: constant.clone(), type_constants });
+    }
+    return entries;
+}
+
+/// Reads the species and their types from a `species_info.h` file of the Emerald decomp, and adds them to the type chart
+///
+/// `TYPE_*` constants are matched with the types of the chart ignoring the case and punctuation, the other ones are added
+/// as new types, in the order of `types_filepath` (like `include/constants/pokemon.h`) if it is given. Species are named
+/// after their constant, `SPECIES_MR_MIME` gives `Mr Mime`. The whole import is a single change in the history,
+/// and anything that was ignored is listed in the report
+pub fn import_emerald_species(type_chart: &mut TypeChart, species_filepath: &String, types_filepath: Option<&String>) -> Result<ImportReport, TrackerError> {
+    let mut report = ImportReport::default();
+    let mut type_constants = match types_filepath {
+        None => Vec::new(),
+        Some(types_filepath) => read_type_constants(types_filepath, &mut report)?,
+    };
+
+    let source = strip_comments(&std::fs::read_to_string(species_filepath)?);
+    let mut species_constants: Vec<(String, Vec<String>)> = Vec::new();
+    for entry in parse_species_entries(&source) {
+        // Placeholder for empty slots, not a Pokemon
+        if entry.constant == "SPECIES_NONE" {
+            continue;
+        }
+        let mut species_types = Vec::new();
+        for constant in entry.type_constants {
+            if !is_type_constant(&constant) {
+                report.repair(entry.line, None, format!("{} of {} isn't a type Pokemon can have, ignored", constant, entry.constant));
+                continue;
+            }
+            if !type_constants.contains(&constant) {
+                if types_filepath.is_some() {
+                    report.repair(entry.line, None, format!("{} of {} isn't defined with the other types, it was added anyway", constant, entry.constant));
+                }
+                type_constants.push(constant.clone());
+            }
+            species_types.push(constant);
+        }
+        if species_types.is_empty() {
+            report.repair(entry.line, None, format!("{} has no types, ignored", entry.constant));
+            continue;
+        }
+        species_constants.push((entry.constant, species_types));
+    }
+
+    let type_list = type_chart.get_type_list();
+    let type_names: Vec<String> = type_constants.iter().map(|constant| type_name_from_constant(&type_list, constant)).collect();
+    let species: Vec<(String, Vec<String>)> = species_constants.iter()
+        .map(|(constant, species_types)| {
+            let species_name = title_case(constant.strip_prefix("SPECIES_").unwrap_or(constant));
+            return (species_name, species_types.iter().map(|species_type| type_name_from_constant(&type_names, species_type)).collect());
+        })
+        .collect();
+    let source_name = Path::new(species_filepath).file_name().map_or(species_filepath.clone(), |file_name| file_name.to_string_lossy().to_string());
+    type_chart.import_species(&source_name, &type_names, &species)?;
+    return Ok(report);
+}
+
+// Items of the table between its braces with their line, the table is the one defined as `gTypeEffectiveness[...] = {`
+fn parse_table_items(source: &str) -> Result<Vec<(usize, &str)>, TrackerError> {
+    let definition = identifiers(source).into_iter().find(|(position, identifier)| {
+        let after = source[position + identifier.len()..].trim_start();
+        let after = match after.strip_prefix('[') {
+            None => after,
+            Some(size) => size.split_once(']').map_or("", |(_, after)| after).trim_start(),
+        };
+        return *identifier == TABLE_NAME && after.starts_with('=');
+    });
+    let table_position = match definition {
+        None => return Err(TrackerError::MalformedRow { line: 1, column: None, reason: format!("No {} table in the file", TABLE_NAME) }),
+        Some((position, _)) => position,
+    };
+    let start = match source[table_position..].find('{') {
+        None => return Err(TrackerError::MalformedRow { line: line_at(source, table_position), column: None, reason: format!("{} has no items", TABLE_NAME) }),
+        Some(offset) => table_position + offset + 1,
+    };
+    let end = match source[start..].find('}') {
+        None => return Err(TrackerError::MalformedRow { line: line_at(source, table_position), column: None, reason: format!("{} is never closed", TABLE_NAME) }),
+        Some(offset) => start + offset,
+    };
+    let mut items = Vec::new();
+    let mut item_start = start;
+    for item in source[start..end].split(',') {
+        if !item.trim().is_empty() {
+            items.push((line_at(source, item_start + item.len() - item.trim_start().len()), item.trim()));
+        }
+        item_start += item.len() + 1;
+    }
+    return Ok(items);
+}
+
+// The multipliers are written with their constants in the decomp, and sometimes as numbers in hacks
+fn parse_table_multiplier(item: &str) -> Option<Multiplier> {
+    match item {
+        "TYPE_MUL_NO_EFFECT" | "0" | "00" => return Some(Multiplier::IMMUNE),
+        "TYPE_MUL_NOT_EFFECTIVE" | "5" | "05" => return Some(Multiplier::NOT_VERY_EFFECTIVE),
+        "TYPE_MUL_NORMAL" | "10" => return Some(Multiplier::NEUTRAL),
+        "TYPE_MUL_SUPER_EFFECTIVE" | "20" => return Some(Multiplier::SUPER_EFFECTIVE),
+        _ => return None,
+    }
+}
+
+/// Reads a type chart from the `gTypeEffectiveness` table of the Emerald decomp (in `src/battle_main.c`)
+///
+/// The table lists `TYPE_A, TYPE_B, TYPE_MUL_*` triplets up to `TYPE_ENDTABLE`, and every matchup that isn't listed
+/// is neutral, so all the matchups of the type chart are known. Types that are neutral against everything don't
+/// appear in the table, `types_filepath` (like `include/constants/pokemon.h`) adds them and gives the order of the types.
+/// Anything that had to be fixed or ignored is listed in the report
+pub fn import_emerald_table(filepath: &String, types_filepath: Option<&String>) -> Result<(TypeChart, ImportReport), TrackerError> {
+    let mut report = ImportReport::default();
+    let mut type_constants = match types_filepath {
+        None => Vec::new(),
+        Some(types_filepath) => read_type_constants(types_filepath, &mut report)?,
+    };
+    let source = strip_comments(&std::fs::read_to_string(filepath)?);
+    let items = parse_table_items(&source)?;
+    // Line of every matchup, to point at the previous one when a matchup is listed twice
+    let mut matchups: BTreeMap<(String, String), (usize, Multiplier)> = BTreeMap::new();
+    let mut ended = false;
+    for triplet in items.chunks(3) {
+        let line = triplet[0].0;
+        let [(_, attacking_constant), (_, defending_constant), (_, multiplier_item)] = triplet else {
+            report.repair(line, None, "Incomplete matchup at the end of the table ignored".to_string());
+            break;
+        };
+        if *attacking_constant == "TYPE_ENDTABLE" {
+            ended = true;
+            break;
+        }
+        // The immunities after this separator are the ones Foresight ignores, they are matchups all the same
+        if *attacking_constant == "TYPE_FORESIGHT" {
+            continue;
+        }
+        if let Some(constant) = [attacking_constant, defending_constant].into_iter().find(|constant| !is_type_constant(constant)) {
+            report.repair(line, None, format!("{} isn't a type Pokemon can have, the matchup was ignored", constant));
+            continue;
+        }
+        let multiplier = match parse_table_multiplier(multiplier_item) {
+            None => {
+                report.repair(line, None, format!("{} isn't a type multiplier, {} against {} was left neutral", multiplier_item, attacking_constant, defending_constant));
+                continue;
+            },
+            Some(multiplier) => multiplier,
+        };
+        for constant in [attacking_constant, defending_constant] {
+            if !type_constants.iter().any(|current_constant| current_constant == constant) {
+                if types_filepath.is_some() {
+                    report.repair(line, None, format!("{} isn't defined with the other types, it was added anyway", constant));
+                }
+                type_constants.push(constant.to_string());
+            }
+        }
+        let cell = (attacking_constant.to_string(), defending_constant.to_string());
+        if let Some((first_line, _)) = matchups.get(&cell) {
+            report.repair(line, None, format!("{} against {} is already listed on line {}, this one is kept", cell.0, cell.1, first_line));
+        }
+        matchups.insert(cell, (line, multiplier));
+    }
+    if !ended {
+        report.repair(items.last().map_or(1, |(line, _)| *line), None, "The table doesn't end with TYPE_ENDTABLE".to_string());
+    }
+
+    let type_names: HashMap<&String, String> = type_constants.iter().map(|constant| (constant, type_name_from_constant(&[], constant))).collect();
+    let type_list: Vec<String> = type_constants.iter().map(|constant| type_names[constant].clone()).collect();
+    let mut type_map: TypeMap = HashMap::new();
+    for attacking_type in &type_list {
+        let effectiveness_map = type_map.entry(attacking_type.clone()).or_default();
+        for defending_type in &type_list {
+            effectiveness_map.insert(defending_type.clone(), Effectiveness::NEUTRAL);
+        }
+    }
+    for ((attacking_constant, defending_constant), (_, multiplier)) in matchups {
+        if let Some(effectiveness_map) = type_map.get_mut(&type_names[&attacking_constant]) {
+            effectiveness_map.insert(type_names[&defending_constant].clone(), Effectiveness::Known(multiplier));
+        }
+    }
+    return Ok((TypeChart::new(type_map, type_list), report));
+}
+
+// Constant of a multiplier in the table, neutral matchups aren't written
+fn table_multiplier(attacking_type: &String, defending_type: &String, multiplier: Multiplier) -> Result<Option<&'static str>, TrackerError> {
+    match multiplier {
+        Multiplier::IMMUNE => return Ok(Some("TYPE_MUL_NO_EFFECT")),
+        Multiplier::NOT_VERY_EFFECTIVE => return Ok(Some("TYPE_MUL_NOT_EFFECTIVE")),
+        Multiplier::NEUTRAL => return Ok(None),
+        Multiplier::SUPER_EFFECTIVE => return Ok(Some("TYPE_MUL_SUPER_EFFECTIVE")),
+        multiplier => return Err(TrackerError::Export(format!("{} against {} is {}, which the table can't hold", attacking_type, defending_type, multiplier))),
+    }
+}
+
+/// Writes the type chart as the `gTypeEffectiveness` table of the Emerald decomp, to compare it with the source of a hack
+/// or paste it in, only the types in `type_filter` are written if it isn't empty
+///
+/// Neutral matchups are left out like in the game, and so are the unknown ones, which the game will treat as neutral.
+/// Suspected matchups are written as if they were confirmed. The immunities of Normal and Fighting against Ghost
+/// go after `TYPE_FORESIGHT` like in the original table, so that Foresight still removes them.
+/// Types whose names give the same `TYPE_*` constant, like `Psychic` and `psychic`, can't be written
+pub fn save_emerald_table(type_chart: &TypeChart, type_filter: &[String], filepath: &String) -> Result<(), TrackerError> {
+    let type_list: Vec<String> = type_chart.get_type_list().into_iter()
+        .filter(|type_name| type_filter.is_empty() || type_filter.contains(type_name))
+        .collect();
+    // Two types written with the same constant would be merged by the game
+    let mut constants: HashMap<String, &String> = HashMap::new();
+    for type_name in &type_list {
+        if let Some(other_type) = constants.insert(type_constant(type_name), type_name) {
+            return Err(TrackerError::Export(format!("Types {} and {} would both be written as {}, rename one of them", other_type, type_name, type_constant(type_name))));
+        }
+    }
+    let mut matchup_lines = Vec::new();
+    let mut foresight_lines = Vec::new();
+    let mut unknown_count = 0;
+    for attacking_type in &type_list {
+        for defending_type in &type_list {
+            let multiplier = match type_chart.get_effectiveness(attacking_type, defending_type)? {
+                Effectiveness::Unknown => {
+                    unknown_count += 1;
+                    continue;
+                },
+                Effectiveness::Known(multiplier) | Effectiveness::Suspected(multiplier) => multiplier,
+            };
+            let Some(table_multiplier) = table_multiplier(attacking_type, defending_type, multiplier)? else {
+                continue;
+            };
+            let (attacking_constant, defending_constant) = (type_constant(attacking_type), type_constant(defending_type));
+            let foresight = multiplier == Multiplier::IMMUNE && ["TYPE_NORMAL", "TYPE_FIGHTING"].contains(&attacking_constant.as_str()) && defending_constant == "TYPE_GHOST";
+            let line = format!("    {}, {}, {},", attacking_constant, defending_constant, table_multiplier);
+            if foresight {
+                foresight_lines.push(line);
+            } else {
+                matchup_lines.push(line);
+            }
+        }
+    }
+
+    let mut lines = vec!["// Exported from the TMT2 type tracker, the matchups that aren't listed are neutral".to_string()];
+    if unknown_count == 1 {
+        lines.push("// 1 matchup is still unknown in the tracker, it is neutral here".to_string());
+    } else if unknown_count > 1 {
+        lines.push(format!("// {} matchups are still unknown in the tracker, they are neutral here", unknown_count));
+    }
+    // Every matchup, the Foresight separator and the end of the table take three items
+    let item_count = (matchup_lines.len() + foresight_lines.len() + 2) * 3;
+    lines.push(format!("const u8 {}[{}] =", TABLE_NAME, item_count));
+    lines.push("{".to_string());
+    lines.extend(matchup_lines);
+    lines.push("    TYPE_FORESIGHT, TYPE_FORESIGHT, TYPE_MUL_NO_EFFECT,".to_string());
+    lines.extend(foresight_lines);
+    lines.push("    TYPE_ENDTABLE, TYPE_ENDTABLE, TYPE_MUL_NO_EFFECT".to_string());
+    lines.push("};".to_string());
+    return write_synced(Path::new(filepath), &(lines.join("\n") + "\n"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::TestDirectory;
+
+    const TABLE_FIXTURE: &str = "\
+// Comments can mention gTypeEffectiveness = { without being the table
+extern const u8 gTypeEffectiveness[];
+const u8 gTypeEffectiveness[30] =
+{
+    TYPE_NORMAL, TYPE_ROCK, TYPE_MUL_NOT_EFFECTIVE,
+    TYPE_FIRE, TYPE_FIRE, TYPE_MUL_NOT_EFFECTIVE, /* same type */
+    TYPE_FIRE, TYPE_GRASS, TYPE_MUL_SUPER_EFFECTIVE,
+    TYPE_WATER, TYPE_FIRE, 20,
+    TYPE_GRASS, TYPE_WATER, TYPE_MUL_SUPER_EFFECTIVE,
+    TYPE_GHOST, TYPE_NORMAL, TYPE_MUL_NO_EFFECT,
+    TYPE_FORESIGHT, TYPE_FORESIGHT, TYPE_MUL_NO_EFFECT,
+    TYPE_NORMAL, TYPE_GHOST, TYPE_MUL_NO_EFFECT,
+    TYPE_FIGHTING, TYPE_GHOST, TYPE_MUL_NO_EFFECT,
+    TYPE_ENDTABLE, TYPE_ENDTABLE, TYPE_MUL_NO_EFFECT
+};
+";
+
+    const TYPES_FIXTURE: &str = "\
+#define TYPE_NORMAL   0
+#define TYPE_FIRE     1
+#define TYPE_MYSTERY  9 // ???
+#define TYPE_GRASS    10
+#define TYPE_PSYCHIC  11
+#define TYPE_DARK     12
+#define NUMBER_OF_MON_TYPES 13
+";
+
+    const SPECIES_FIXTURE: &str = "\
+#define OLD_UNOWN_SPECIES_INFO { .type1 = TYPE_PSYCHIC, }
+const struct SpeciesInfo gSpeciesInfo[] =
+{
+    [SPECIES_NONE] = {0},
+    [SPECIES_BULBASAUR] =
+    {
+        .baseHP = 45,
+        .types = { TYPE_GRASS, TYPE_POISON },
+        // .types = { TYPE_FIRE, TYPE_FIRE },
+    },
+    [SPECIES_MR_MIME] =
+    {
+        .type1 = TYPE_PSYCHIC,
+        .type2 = TYPE_PSYCHIC,
+        .abilities = {ABILITY_SOUNDPROOF, ABILITY_NONE},
+    },
+    [SPECIES_MIGHTYENA] = { .types = MON_TYPES(TYPE_DARK), .speciesName = _(\"Mightyena // not a comment\") },
+    [SPECIES_PORYGON] = { .types = { TYPE_NORMAL, TYPE_NONE } },
+    [SPECIES_OLD_UNOWN_B] = OLD_UNOWN_SPECIES_INFO,
+};
+";
+
+    fn species_types(type_chart: &TypeChart, species_name: &str) -> Vec<String> {
+        return type_chart.get_species_types(species_name).unwrap();
+    }
+
+    #[test]
+    fn species_are_read_with_their_types() {
+        let test_directory = TestDirectory::new("emerald_species");
+        let species_filepath = test_directory.write_file("species_info.h", SPECIES_FIXTURE);
+        let types_filepath = test_directory.write_file("pokemon.h", TYPES_FIXTURE);
+        let mut type_chart = TypeChart::empty();
+        // Matched with the existing type whatever its case
+        type_chart.add_new_type(&"psychic".to_string()).unwrap();
+        let report = import_emerald_species(&mut type_chart, &species_filepath, Some(&types_filepath)).unwrap();
+
+        assert_eq!(type_chart.get_type_list(), ["psychic", "Normal", "Fire", "Grass", "Dark", "Poison"].map(String::from));
+        assert_eq!(species_types(&type_chart, "Bulbasaur"), ["Grass", "Poison"].map(String::from));
+        // The same type twice is a single type
+        assert_eq!(species_types(&type_chart, "Mr Mime"), ["psychic"].map(String::from));
+        assert_eq!(species_types(&type_chart, "Mightyena"), ["Dark"].map(String::from));
+        assert_eq!(species_types(&type_chart, "Porygon"), ["Normal"].map(String::from));
+        assert_eq!(type_chart.get_species().len(), 4);
+
+        let descriptions: Vec<String> = report.repairs.iter().map(|repair| repair.to_string()).collect();
+        assert_eq!(descriptions.len(), 4, "{:?}", descriptions);
+        assert!(descriptions[0].starts_with("line 3: TYPE_MYSTERY"));
+        assert!(descriptions[1].starts_with("line 5: TYPE_POISON of SPECIES_BULBASAUR isn't defined"));
+        assert!(descriptions[2].starts_with("line 18: TYPE_NONE of SPECIES_PORYGON"));
+        assert_eq!(descriptions[3], "line 19: SPECIES_OLD_UNOWN_B has no types, ignored");
+    }
+
+    #[test]
+    fn species_import_is_undone_at_once() {
+        let test_directory = TestDirectory::new("emerald_species_undo");
+        let species_filepath = test_directory.write_file("species_info_undo.h", SPECIES_FIXTURE);
+        let mut type_chart = TypeChart::empty();
+        import_emerald_species(&mut type_chart, &species_filepath, None).unwrap();
+        assert_eq!(type_chart.get_history().len(), 1);
+        // Importing the same file again changes nothing
+        import_emerald_species(&mut type_chart, &species_filepath, None).unwrap();
+        assert_eq!(type_chart.get_history().len(), 1);
+        type_chart.undo();
+        assert!(type_chart.get_type_list().is_empty());
+        assert!(type_chart.get_species().is_empty());
+    }
+
+    fn effectiveness(type_chart: &TypeChart, attacking_type: &str, defending_type: &str) -> Effectiveness {
+        return type_chart.get_effectiveness(&attacking_type.to_string(), &defending_type.to_string()).unwrap();
+    }
+
+    #[test]
+    fn table_is_read_with_every_missing_matchup_neutral() {
+        let test_directory = TestDirectory::new("emerald_table");
+        let filepath = test_directory.write_file("read.c", TABLE_FIXTURE);
+        let (type_chart, report) = import_emerald_table(&filepath, None).unwrap();
+        assert!(report.repairs.is_empty(), "{:?}", report.repairs);
+        assert_eq!(type_chart.get_type_list(), ["Normal", "Rock", "Fire", "Grass", "Water", "Ghost", "Fighting"].map(String::from));
+        assert_eq!(effectiveness(&type_chart, "Fire", "Grass"), Effectiveness::SUPER_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Water", "Fire"), Effectiveness::SUPER_EFFECTIVE);
+        // Listed after TYPE_FORESIGHT
+        assert_eq!(effectiveness(&type_chart, "Fighting", "Ghost"), Effectiveness::IMMUNE);
+        assert_eq!(effectiveness(&type_chart, "Grass", "Fire"), Effectiveness::NEUTRAL);
+    }
+
+    #[test]
+    fn exported_table_reads_back_the_same() {
+        let test_directory = TestDirectory::new("emerald_round_trip");
+        let filepath = test_directory.write_file("round_trip.c", TABLE_FIXTURE);
+        let (type_chart, _) = import_emerald_table(&filepath, None).unwrap();
+        let exported_filepath = test_directory.filepath("round_trip_exported.c");
+        save_emerald_table(&type_chart, &[], &exported_filepath).unwrap();
+        let exported = std::fs::read_to_string(&exported_filepath).unwrap();
+        assert!(exported.contains("const u8 gTypeEffectiveness[30] ="));
+        // The Foresight immunities stay after the separator
+        let foresight_position = exported.find("TYPE_FORESIGHT").unwrap();
+        assert!(exported.find("TYPE_FIGHTING, TYPE_GHOST").unwrap() > foresight_position);
+        assert!(exported.find("TYPE_GHOST, TYPE_NORMAL").unwrap() < foresight_position);
+
+        let (read_back, report) = import_emerald_table(&exported_filepath, None).unwrap();
+        assert!(report.repairs.is_empty(), "{:?}", report.repairs);
+        assert_eq!(read_back.get_type_list(), type_chart.get_type_list());
+        assert_eq!(read_back.get_type_map(), type_chart.get_type_map());
+    }
+
+    #[test]
+    fn types_with_the_same_constant_are_refused() {
+        let test_directory = TestDirectory::new("emerald_same_constant");
+        let mut type_chart = TypeChart::empty();
+        for type_name in ["Psychic", "psychic"] {
+            type_chart.add_new_type(&type_name.to_string()).unwrap();
+        }
+        let filepath = test_directory.filepath("same_constant.c");
+        assert!(matches!(save_emerald_table(&type_chart, &[], &filepath), Err(TrackerError::Export(_))));
+        assert!(!std::fs::exists(&filepath).unwrap());
+        // Leaving one of them out is fine
+        save_emerald_table(&type_chart, &["Psychic".to_string()], &filepath).unwrap();
+    }
+
+    #[test]
+    fn table_problems_are_reported() {
+        let test_directory = TestDirectory::new("emerald_problems");
+        let filepath = test_directory.write_file("problems.c", "\
+const u8 gTypeEffectiveness[] =
+{
+    TYPE_FIRE, TYPE_GRASS, TYPE_MUL_SUPER_EFFECTIVE,
+    TYPE_FIRE, TYPE_MYSTERY, TYPE_MUL_NO_EFFECT,
+    TYPE_FIRE, TYPE_WATER, TYPE_MUL_HALF,
+    TYPE_FIRE, TYPE_GRASS, TYPE_MUL_NOT_EFFECTIVE,
+    TYPE_WATER, TYPE_FIRE
+};
+");
+        let (type_chart, report) = import_emerald_table(&filepath, None).unwrap();
+        let lines: Vec<usize> = report.repairs.iter().map(|repair| repair.line).collect();
+        // Mystery type, unknown multiplier, matchup listed twice, incomplete matchup and missing end of the table
+        assert_eq!(lines, vec![4, 5, 6, 7, 7]);
+        assert_eq!(effectiveness(&type_chart, "Fire", "Grass"), Effectiveness::NOT_VERY_EFFECTIVE);
+    }
+}
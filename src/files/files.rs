@@ -1,155 +1,182 @@
-use std::collections::HashMap;
-
-use crate::type_chart::{TypeChart, TypeMap, effectiveness_f32_to_string, effectiveness_string_to_f32};
-
-pub fn get_types_from_file(filepath: &String) -> Result<TypeChart, ()> {
-    // Maybe should handle empty file? => return empty typechart
-    match std::fs::exists(filepath) {
-        Err(err) => {
-            eprintln!("Could not check if file exists :");
-            eprintln!("{}", err.to_string());
-            return Err(());
-        },
-        Ok(false) => return Ok(TypeChart::empty()),
-        _ => (),
+use std::{io::Write, path::{Path, PathBuf}};
+
+use crate::{error::TrackerError, files::{backups::back_up_file, chart_file::ChartFile, discovery_log::append_to_discovery_log, legacy_csv::read_legacy_csv, species_file::{read_species_file, write_species_file}}, type_chart::TypeChart};
+
+/// Formats a type chart can be loaded from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChartFormat {
+    /// Versioned JSON chart file, the only format the tracker saves in
+    Json,
+    /// Headerless CSV matrix written by the first versions of the tracker
+    LegacyCsv,
+}
+
+fn detect_format_from_content(content: &str) -> ChartFormat {
+    // A legacy CSV file starts with a type name, it can't start with a JSON object
+    if content.trim_start().starts_with('{') {
+        return ChartFormat::Json;
     }
-    let mut reader_builder = csv::ReaderBuilder::new();
-    // Disable headers to handle them manually
-    let mut file_reader = match reader_builder.has_headers(false).from_path(filepath) {
-        Err(err) => {
-            eprintln!("Could not open and read the file:");
-            eprintln!("{}", err.to_string());
-            return Err(());
-        },
-        Ok(file_content) => file_content,
-    };
-    let mut type_list: Vec<String> = Vec::new();
-    let mut hashmap: TypeMap = HashMap::new();
-    
-    let mut index: usize = 0;
-    for line in file_reader.records() {
-        let line = match line {
-            Err(err) => {
-                eprintln!("There was an error on line {}:", index);
-                eprintln!("{}", err.to_string());
-                continue;
-            }
-            Ok(line) => line,
-        };
-        if index == 0 {
-            for new_type in line.iter() {
-                hashmap.insert(new_type.trim().to_string(), HashMap::new());
-                type_list.push(new_type.trim().to_string());
-            }
-        } else {
-            let current_type = match type_list.get(index - 1) {
-                None => {
-                    // Can only happen if there are more lines than types
-                    eprintln!("Error while trying to load file : There are more lines than types");
-                    return Err(());
-                },
-                Some(current_type) => current_type,
-            };
-
-            let type_matchups = match hashmap.get_mut(current_type) {
-                None => {
-                    // Should never happen?
-                    eprintln!("The type existed in the list but not in the hashmap");
-                    return Err(());
-                },
-                Some(type_matchups) => type_matchups,
-            };
-
-            let mut line_index: usize = 0;
-            for effectiveness in line.iter() {
-                let opposing_type = match type_list.get(line_index) {
-                    None => {
-                        // Can only happen if there are more lines than types
-                        eprintln!("Error while trying to load file : There are more lines than types");
-                        return Err(());
-                    },
-                    Some(opposing_type) => opposing_type,
-                };
-                let effectiveness_value: f32 = match effectiveness_string_to_f32(&effectiveness.to_string()) {
-                    Err(_) => {
-                        // Can only happen if the file contains incorrect effectivenesses
-                        eprintln!("Error while trying to load file : Effectiveness {} doesn't exist", effectiveness);
-                        return Err(());
-                    },
-                    Ok(effectiveness_value) => effectiveness_value,
-                };
-                type_matchups.insert(opposing_type.clone(), effectiveness_value);
-                line_index += 1;
-            }
-        }
-        index += 1;
+    return ChartFormat::LegacyCsv;
+}
+
+/// Looks at the content of a chart file to know its format, missing and empty files are considered up to date
+pub fn detect_format(filepath: &String) -> Result<ChartFormat, TrackerError> {
+    if !std::fs::exists(filepath)? {
+        return Ok(ChartFormat::Json);
+    }
+    let content = std::fs::read_to_string(filepath)?;
+    if content.trim().is_empty() {
+        return Ok(ChartFormat::Json);
     }
+    return Ok(detect_format_from_content(&content));
+}
 
-    return Ok(TypeChart::new(hashmap, type_list));
+/// Loads a type chart from a chart file in any supported format, a missing or empty file gives an empty type chart
+///
+/// The species kept next to the chart file are loaded with it
+pub fn get_types_from_file(filepath: &String) -> Result<TypeChart, TrackerError> {
+    let mut type_chart = read_chart_file(filepath)?;
+    type_chart.species = read_species_file(filepath)?;
+    return Ok(type_chart);
 }
 
-pub fn save_types_to_file(type_chart: &TypeChart, filepath: &String) -> Result<(), ()> {
-    if type_chart.is_empty() {
-        if let Err(err) = std::fs::File::create(filepath) {
-            eprintln!("Could not create file to store empty type chart :");
-            eprintln!("{}", err.to_string());
-            return Err(());
-        }
-        return Ok(());
+fn read_chart_file(filepath: &String) -> Result<TypeChart, TrackerError> {
+    if !std::fs::exists(filepath)? {
+        return Ok(TypeChart::empty());
     }
-    let mut writer_builder = csv::WriterBuilder::new();
-    let mut file_writer = match writer_builder.has_headers(false).from_path(filepath) {
-        Err(err) => {
-            eprintln!("Could not open and write the file:");
-            eprintln!("{}", err.to_string());
-            return Err(());
-        },
-        Ok(file_writer) => file_writer,
-    };
-    // First write the type list
-    let type_list = type_chart.get_type_list();
-    if let Err(err) = file_writer.write_record(&type_list) {
-        eprintln!("Error while trying to write the types in the file:");
-        eprintln!("{}", err.to_string());
-        return Err(());
+    let content = std::fs::read_to_string(filepath)?;
+    if content.trim().is_empty() {
+        return Ok(TypeChart::empty());
     }
+    match detect_format_from_content(&content) {
+        ChartFormat::Json => return ChartFile::from_json(&content)?.into_type_chart(),
+        ChartFormat::LegacyCsv => return read_legacy_csv(&content),
+    }
+}
 
-    let type_map =  type_chart.get_type_map();
-    for current_type in &type_list {
-        // Converting the map into a list ordered the same way that the type list is
-        let effectiveness_map = match type_map.get(current_type) {
-            None => {
-                eprintln!("Error while trying to get effectiveness of type {}", current_type);
-                return Err(());
-            },
-            Some(effectiveness_map) => effectiveness_map,
-        };
-        let mut effectiveness_list: Vec<String> = Vec::new();
-        for opposing_type in &type_list {
-            let effectiveness = match effectiveness_map.get(opposing_type) {
-                None => {
-                    eprintln!("Error while trying to get effectiveness of type {} versus {}", current_type, opposing_type);
-                    return Err(());
-                },
-                Some(effectiveness) => effectiveness,
-            };
-            effectiveness_list.push(
-                match effectiveness_f32_to_string(*effectiveness, 0) {
-                    Err(_) => {
-                        // Should never happen
-                        eprintln!("Error while trying to save type chart to file: Effectiveness {} doesn't exist", effectiveness);
-                        return Err(());
-                    },
-                    Ok(effectiveness_string) => effectiveness_string,
-                }.to_string()
-            );
-        }
-        if let Err(err) = file_writer.write_record(&effectiveness_list) {
-            eprintln!("Error while trying to write the effectiveness of type {}", current_type);
-            eprintln!("{}", err.to_string());
-            return Err(());
-        }
+/// Saves a type chart in the versioned JSON chart format
+///
+/// The type chart is written to a temporary file that then replaces the chart file, so the chart file
+/// is never left half written, and the previous version of the chart file is kept as a backup.
+/// The matchups learned since the type chart was loaded are added to its discovery log, and the species are saved next to it
+pub fn save_types_to_file(type_chart: &TypeChart, filepath: &String) -> Result<(), TrackerError> {
+    let mut content = serde_json::to_string_pretty(&ChartFile::from_type_chart(type_chart))?;
+    content.push('\n');
+    let file_exists = std::fs::exists(filepath)?;
+    // Nothing changed, no need to write the file or make a backup
+    if file_exists && std::fs::read_to_string(filepath).is_ok_and(|current_content| current_content == content) {
+        write_species_file(filepath, &type_chart.species)?;
+        return append_to_discovery_log(filepath, &type_chart.discoveries);
+    }
+
+    let temporary_filepath = temporary_filepath(Path::new(filepath));
+    if let Err(err) = write_synced(&temporary_filepath, &content) {
+        let _ = std::fs::remove_file(&temporary_filepath);
+        return Err(err);
+    }
+    if file_exists {
+        back_up_file(filepath)?;
+    }
+    std::fs::rename(&temporary_filepath, filepath)?;
+    // Only once the chart is saved, a failed chart write leaves both files as they were
+    write_species_file(filepath, &type_chart.species)?;
+    append_to_discovery_log(filepath, &type_chart.discoveries)?;
+    return Ok(());
+}
+
+// The temporary file is in the same directory so that renaming it can't move it to another file system
+pub(crate) fn temporary_filepath(filepath: &Path) -> PathBuf {
+    let mut temporary_name = std::ffi::OsString::from(".");
+    temporary_name.push(filepath.file_name().unwrap_or_default());
+    temporary_name.push(".tmp");
+    return filepath.with_file_name(temporary_name);
+}
+
+pub(crate) fn write_synced(filepath: &Path, content: &str) -> Result<(), TrackerError> {
+    let mut file = std::fs::File::create(filepath)?;
+    file.write_all(content.as_bytes())?;
+    // Making sure the content is on the disk before the file replaces the chart file
+    file.sync_all()?;
+    return Ok(());
+}
+
+/// Path a legacy chart file is migrated to, the same path with a .json extension
+pub fn migrated_filepath(legacy_filepath: &String) -> String {
+    return Path::new(legacy_filepath).with_extension("json").to_string_lossy().to_string();
+}
+
+/// Converts a chart file of any format to the versioned JSON chart format, the original file is left untouched
+pub fn migrate_legacy_file(legacy_filepath: &String, new_filepath: &String) -> Result<TypeChart, TrackerError> {
+    let type_chart = get_types_from_file(legacy_filepath)?;
+    save_types_to_file(&type_chart, new_filepath)?;
+    return Ok(type_chart);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{files::backups::{list_backups, MAX_BACKUPS}, test_helpers::{type_chart_with, TestDirectory}};
+
+    #[test]
+    fn the_legacy_example_reads_like_the_json_example() {
+        let legacy_type_chart = read_legacy_csv(include_str!("../../examples/types.csv")).unwrap();
+        let type_chart = ChartFile::from_json(include_str!("../../examples/types.json")).unwrap().into_type_chart().unwrap();
+        assert_eq!(legacy_type_chart.get_type_list(), type_chart.get_type_list());
+        assert_eq!(legacy_type_chart.get_type_map(), type_chart.get_type_map());
     }
 
-    return Ok(())
+    #[test]
+    fn migrating_writes_a_json_file_and_keeps_the_legacy_file() {
+        let test_directory = TestDirectory::new("files_migration");
+        let legacy_content = "Fire,Water\nNeutral,Not Very Effective\nSuper Effective,?\n";
+        let legacy_filepath = test_directory.write_file("types.csv", legacy_content);
+        assert_eq!(detect_format(&legacy_filepath).unwrap(), ChartFormat::LegacyCsv);
+
+        let new_filepath = migrated_filepath(&legacy_filepath);
+        assert_eq!(new_filepath, test_directory.filepath("types.json"));
+        let type_chart = migrate_legacy_file(&legacy_filepath, &new_filepath).unwrap();
+        assert_eq!(detect_format(&new_filepath).unwrap(), ChartFormat::Json);
+        assert_eq!(get_types_from_file(&new_filepath).unwrap().get_type_map(), type_chart.get_type_map());
+        assert_eq!(std::fs::read_to_string(&legacy_filepath).unwrap(), legacy_content);
+    }
+
+    #[test]
+    fn saving_replaces_the_chart_file_and_backs_up_the_previous_one() {
+        let test_directory = TestDirectory::new("files_atomic_save");
+        let filepath = test_directory.filepath("types.json");
+        let mut type_chart = type_chart_with(&["Fire"]);
+        save_types_to_file(&type_chart, &filepath).unwrap();
+        // There was nothing to back up yet
+        assert!(list_backups(&filepath).unwrap().is_empty());
+
+        type_chart.add_new_type(&"Water".to_string()).unwrap();
+        save_types_to_file(&type_chart, &filepath).unwrap();
+        assert!(!std::fs::exists(temporary_filepath(Path::new(&filepath))).unwrap());
+        assert_eq!(get_types_from_file(&filepath).unwrap().get_type_list(), vec!["Fire".to_string(), "Water".to_string()]);
+        let backups = list_backups(&filepath).unwrap();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(get_types_from_file(&backups[0].filepath).unwrap().get_type_list(), vec!["Fire".to_string()]);
+
+        // Saving the same chart again changes nothing
+        save_types_to_file(&type_chart, &filepath).unwrap();
+        assert_eq!(list_backups(&filepath).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn only_the_most_recent_backups_are_kept() {
+        let test_directory = TestDirectory::new("files_backup_rotation");
+        let filepath = test_directory.filepath("types.json");
+        let mut type_chart = type_chart_with(&[]);
+        for index in 0..MAX_BACKUPS + 3 {
+            type_chart.add_new_type(&format!("Type {}", index)).unwrap();
+            save_types_to_file(&type_chart, &filepath).unwrap();
+            // Backups are named after the millisecond they were made at
+            std::thread::sleep(std::time::Duration::from_millis(2));
+        }
+        let backups = list_backups(&filepath).unwrap();
+        assert_eq!(backups.len(), MAX_BACKUPS);
+        // The most recent backup is the chart before the last save
+        assert_eq!(get_types_from_file(&backups[0].filepath).unwrap().get_type_list().len(), MAX_BACKUPS + 2);
+        assert_eq!(get_types_from_file(&backups[MAX_BACKUPS - 1].filepath).unwrap().get_type_list().len(), 3);
+    }
 }
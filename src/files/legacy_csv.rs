@@ -0,0 +1,65 @@
+use std::collections::HashMap;
+
+use crate::{error::TrackerError, type_chart::{Effectiveness, TypeChart, TypeMap}};
+
+/// Reads a type chart from the headerless CSV format used before chart files were versioned
+///
+/// The first line lists every type, then each line gives the effectiveness of the type
+/// at the same position when attacking every type of the first line
+pub(crate) fn read_legacy_csv(content: &str) -> Result<TypeChart, TrackerError> {
+    let mut reader_builder = csv::ReaderBuilder::new();
+    // Disable headers to handle them manually
+    let mut file_reader = reader_builder.has_headers(false).from_reader(content.as_bytes());
+    let mut type_list: Vec<String> = Vec::new();
+    let mut hashmap: TypeMap = HashMap::new();
+
+    for (index, line) in file_reader.records().enumerate() {
+        let line = match line {
+            Err(err) => {
+                let line = err.position().map_or(index + 1, |position| position.line() as usize);
+                return Err(TrackerError::MalformedRow { line, column: None, reason: err.to_string() });
+            }
+            Ok(line) => line,
+        };
+        if index == 0 {
+            for new_type in line.iter() {
+                if type_list.iter().any(|current_type| current_type == new_type.trim()) {
+                    return Err(TrackerError::DuplicateType(new_type.trim().to_string()));
+                }
+                hashmap.insert(new_type.trim().to_string(), HashMap::new());
+                type_list.push(new_type.trim().to_string());
+            }
+        } else {
+            let current_type = match type_list.get(index - 1) {
+                None => {
+                    // Can only happen if there are more lines than types
+                    return Err(TrackerError::MalformedRow { line: index + 1, column: None, reason: "There are more lines than types".to_string() });
+                },
+                Some(current_type) => current_type,
+            };
+
+            // The type was inserted in the hashmap at the same time as in the list
+            let type_matchups = hashmap.entry(current_type.clone()).or_default();
+
+            for (line_index, effectiveness) in line.iter().enumerate() {
+                let opposing_type = match type_list.get(line_index) {
+                    None => {
+                        // Can only happen if there are more columns than types
+                        return Err(TrackerError::MalformedRow { line: index + 1, column: Some(line_index + 1), reason: "There are more columns than types".to_string() });
+                    },
+                    Some(opposing_type) => opposing_type,
+                };
+                let effectiveness_value = match effectiveness.parse::<Effectiveness>() {
+                    Err(err) => {
+                        // Can only happen if the file contains incorrect effectivenesses
+                        return Err(TrackerError::MalformedRow { line: index + 1, column: Some(line_index + 1), reason: err.to_string() });
+                    },
+                    Ok(effectiveness_value) => effectiveness_value,
+                };
+                type_matchups.insert(opposing_type.clone(), effectiveness_value);
+            }
+        }
+    }
+
+    return Ok(TypeChart::new(hashmap, type_list));
+}
@@ -1,2 +1,15 @@
-pub mod files;
-pub use files::{get_types_from_file, save_types_to_file};
+mod backups;
+mod chart_file;
+mod csv_import;
+mod discovery_log;
+mod emerald_source;
+mod files;
+mod legacy_csv;
+mod species_file;
+pub use backups::{Backup, MAX_BACKUPS, backups_directory, list_backups, read_backup};
+pub use chart_file::FORMAT_VERSION;
+pub use csv_import::{ImportRepair, ImportReport, import_labelled_csv};
+pub use discovery_log::{discovery_log_filepath, read_discovery_log, replay_discoveries};
+pub use emerald_source::{import_emerald_species, import_emerald_table, save_emerald_table, type_name_from_constant};
+pub use files::{ChartFormat, detect_format, get_types_from_file, migrate_legacy_file, migrated_filepath, save_types_to_file};
+pub use species_file::species_filepath;
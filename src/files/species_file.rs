@@ -0,0 +1,45 @@
+use std::{collections::BTreeMap, path::{Path, PathBuf}};
+
+use crate::{error::TrackerError, files::files::{temporary_filepath, write_synced}};
+
+/// File the species of a chart file are kept in, next to the chart file
+///
+/// Species are written by name with the names of their types, so the file can be edited by hand
+pub fn species_filepath(filepath: &String) -> PathBuf {
+    let mut species_name = Path::new(filepath).file_name().unwrap_or_default().to_os_string();
+    species_name.push(".species.json");
+    return Path::new(filepath).with_file_name(species_name);
+}
+
+pub(crate) fn read_species_file(filepath: &String) -> Result<BTreeMap<String, Vec<String>>, TrackerError> {
+    let species_filepath = species_filepath(filepath);
+    if !std::fs::exists(&species_filepath)? {
+        return Ok(BTreeMap::new());
+    }
+    let content = std::fs::read_to_string(&species_filepath)?;
+    if content.trim().is_empty() {
+        return Ok(BTreeMap::new());
+    }
+    return Ok(serde_json::from_str(&content)?);
+}
+
+// Only written when the species changed, and never created for a type chart without species
+pub(crate) fn write_species_file(filepath: &String, species: &BTreeMap<String, Vec<String>>) -> Result<(), TrackerError> {
+    let species_filepath = species_filepath(filepath);
+    let file_exists = std::fs::exists(&species_filepath)?;
+    if species.is_empty() && !file_exists {
+        return Ok(());
+    }
+    let mut content = serde_json::to_string_pretty(species)?;
+    content.push('\n');
+    if file_exists && std::fs::read_to_string(&species_filepath).is_ok_and(|current_content| current_content == content) {
+        return Ok(());
+    }
+    let temporary_filepath = temporary_filepath(&species_filepath);
+    if let Err(err) = write_synced(&temporary_filepath, &content) {
+        let _ = std::fs::remove_file(&temporary_filepath);
+        return Err(err);
+    }
+    std::fs::rename(&temporary_filepath, &species_filepath)?;
+    return Ok(());
+}
@@ -0,0 +1,34 @@
+//! Tracks the type chart of Too Many Types 2 as you discover it while playing.
+//!
+//! The [`TypeChart`] stores how effective every type is against every other type,
+//! [`files`] loads and saves it, [`export`] draws it as an image, and [`Effectiveness`] describes a single matchup.
+//! [`damage`] works out type multipliers from the damage seen in battle.
+//!
+//! ```no_run
+//! use tmt2_type_tracker::{files, Effectiveness};
+//!
+//! let filepath = "examples/types.json".to_string();
+//! let mut type_chart = files::get_types_from_file(&filepath)?;
+//! type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE)?;
+//! files::save_types_to_file(&type_chart, &filepath)?;
+//! # Ok::<(), tmt2_type_tracker::TrackerError>(())
+//! ```
+#![allow(clippy::needless_return, clippy::module_inception, clippy::ptr_arg)]
+#![warn(missing_docs)]
+
+/// Type multipliers worked out from damage numbers
+pub mod damage;
+/// Errors returned by the tracker
+pub mod error;
+/// Drawing the type chart as an image
+pub mod export;
+/// Loading, saving, importing and backing up type charts
+pub mod files;
+/// The type chart and everything that edits it
+pub mod type_chart;
+
+#[cfg(test)]
+mod test_helpers;
+
+pub use error::TrackerError;
+pub use type_chart::{BattleMessage, CombinedEffectiveness, Contradiction, Coverage, Deduction, Discovery, Edit, Effectiveness, EffectivenessMap, Inference, JournalEntry, MergeConflict, Multiplier, Observation, TypeChart, TypeMap};
@@ -0,0 +1,44 @@
+// Fixtures shared by the unit tests
+use std::path::PathBuf;
+
+use crate::type_chart::TypeChart;
+
+// A type chart with these types and every matchup unknown
+pub(crate) fn type_chart_with(type_names: &[&str]) -> TypeChart {
+    let mut type_chart = TypeChart::empty();
+    for type_name in type_names {
+        type_chart.add_new_type(&type_name.to_string()).unwrap();
+    }
+    return type_chart;
+}
+
+// A directory of its own for a test, emptied first in case a previous run failed and removed when the test ends
+pub(crate) struct TestDirectory {
+    path: PathBuf,
+}
+
+impl TestDirectory {
+    pub(crate) fn new(test_name: &str) -> TestDirectory {
+        let path = std::env::temp_dir().join(format!("tmt2_{}_{}", test_name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&path);
+        std::fs::create_dir_all(&path).unwrap();
+        return TestDirectory { path };
+    }
+
+    pub(crate) fn filepath(&self, file_name: &str) -> String {
+        return self.path.join(file_name).to_string_lossy().to_string();
+    }
+
+    // Writes a file in the directory and returns its path
+    pub(crate) fn write_file(&self, file_name: &str, content: &str) -> String {
+        let filepath = self.filepath(file_name);
+        std::fs::write(&filepath, content).unwrap();
+        return filepath;
+    }
+}
+
+impl Drop for TestDirectory {
+    fn drop(&mut self) {
+        let _ = std::fs::remove_dir_all(&self.path);
+    }
+}
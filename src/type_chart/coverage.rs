@@ -0,0 +1,112 @@
+use std::{cmp::Reverse, collections::BTreeMap};
+
+use crate::{error::TrackerError, type_chart::{CombinedEffectiveness, EffectivenessMap, Multiplier, TypeChart}};
+
+/// Best effectiveness a set of attacking types achieves against every defender
+#[derive(Debug, Clone)]
+pub struct Coverage {
+    /// Defenders grouped by the best effectiveness any of the attacking types has against them,
+    /// defenders with several types are written as `First/Second`
+    pub best_effectiveness: EffectivenessMap,
+    /// Defenders that resist or are immune to every attacking type
+    pub walled: Vec<String>,
+}
+
+impl TypeChart {
+    /// Computes the best effectiveness the attacking types have against every type, and every
+    /// combination of up to `max_defending_types` types
+    ///
+    /// Unknown matchups count as neutral and are reported in [`CombinedEffectiveness::unknown_count`]
+    pub fn get_offensive_coverage(&self, attacking_type_names: &[String], max_defending_types: usize) -> Result<Coverage, TrackerError> {
+        let mut attacking_effectiveness_maps = Vec::new();
+        for type_name in attacking_type_names {
+            match self.type_map.get(type_name) {
+                None => return Err(TrackerError::UnknownType(type_name.clone())),
+                Some(effectiveness_map) => attacking_effectiveness_maps.push(effectiveness_map),
+            }
+        }
+        let mut coverage = Coverage { best_effectiveness: BTreeMap::new(), walled: Vec::new() };
+        if attacking_effectiveness_maps.is_empty() {
+            return Ok(coverage);
+        }
+
+        for nb_types in 1..=max_defending_types.min(self.type_list.len()) {
+            for defending_types in type_combinations(&self.type_list, nb_types) {
+                let best_effectiveness = attacking_effectiveness_maps.iter()
+                    .map(|effectiveness_map| CombinedEffectiveness::combine(defending_types.iter().filter_map(|type_name| effectiveness_map.get(*type_name))))
+                    // On equal multipliers, the one we are the most sure about wins
+                    .max_by_key(|effectiveness| (effectiveness.multiplier, Reverse(effectiveness.unknown_count), Reverse(effectiveness.suspected_count)))
+                    .expect("There is at least one attacking type");
+                let defender_name = defending_types.iter().map(|type_name| type_name.as_str()).collect::<Vec<&str>>().join("/");
+                if best_effectiveness.multiplier < Multiplier::NEUTRAL {
+                    coverage.walled.push(defender_name.clone());
+                }
+                coverage.best_effectiveness.entry(best_effectiveness).or_default().push(defender_name);
+            }
+        }
+        return Ok(coverage);
+    }
+}
+
+// Every way to pick `size` different types, keeping the order of the type list
+fn type_combinations(type_list: &[String], size: usize) -> Vec<Vec<&String>> {
+    if size == 0 {
+        return vec![Vec::new()];
+    }
+    let mut combinations = Vec::new();
+    for (index, type_name) in type_list.iter().enumerate() {
+        for mut combination in type_combinations(&type_list[index + 1..], size - 1) {
+            combination.insert(0, type_name);
+            combinations.push(combination);
+        }
+    }
+    return combinations;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::type_chart_with, type_chart::Effectiveness};
+
+    fn coverage_type_chart() -> TypeChart {
+        let mut type_chart = type_chart_with(&["Fire", "Water", "Grass"]);
+        let matchups = [
+            ("Fire", "Fire", Effectiveness::NOT_VERY_EFFECTIVE),
+            ("Fire", "Water", Effectiveness::NOT_VERY_EFFECTIVE),
+            ("Fire", "Grass", Effectiveness::SUPER_EFFECTIVE),
+            ("Water", "Fire", Effectiveness::SUPER_EFFECTIVE),
+            ("Water", "Water", Effectiveness::NOT_VERY_EFFECTIVE),
+            ("Water", "Grass", Effectiveness::NOT_VERY_EFFECTIVE),
+        ];
+        for (attacking_type, defending_type, effectiveness) in matchups {
+            type_chart.add_effectiveness(&attacking_type.to_string(), &defending_type.to_string(), effectiveness).unwrap();
+        }
+        return type_chart;
+    }
+
+    // Defenders the attacking types hit with this multiplier at best, and how many unknown matchups that relies on
+    fn defenders(coverage: &Coverage, multiplier: Multiplier) -> Vec<(String, usize)> {
+        return coverage.best_effectiveness.iter()
+            .filter(|(effectiveness, _)| effectiveness.multiplier == multiplier)
+            .flat_map(|(effectiveness, defenders)| defenders.iter().map(|defender| (defender.clone(), effectiveness.unknown_count)))
+            .collect();
+    }
+
+    #[test]
+    fn the_best_attacking_type_is_kept_for_every_defender() {
+        let type_chart = coverage_type_chart();
+        let coverage = type_chart.get_offensive_coverage(&["Fire".to_string(), "Water".to_string()], 2).unwrap();
+        assert_eq!(defenders(&coverage, Multiplier::SUPER_EFFECTIVE), vec![("Fire".to_string(), 0), ("Grass".to_string(), 0)]);
+        assert_eq!(defenders(&coverage, Multiplier::NEUTRAL), vec![("Fire/Water".to_string(), 0), ("Fire/Grass".to_string(), 0), ("Water/Grass".to_string(), 0)]);
+        assert_eq!(coverage.walled, vec!["Water".to_string()]);
+    }
+
+    #[test]
+    fn unknown_matchups_count_as_neutral() {
+        let type_chart = coverage_type_chart();
+        let coverage = type_chart.get_offensive_coverage(&["Grass".to_string()], 1).unwrap();
+        assert_eq!(defenders(&coverage, Multiplier::NEUTRAL), vec![("Fire".to_string(), 1), ("Water".to_string(), 1), ("Grass".to_string(), 1)]);
+        assert!(coverage.walled.is_empty());
+        assert!(matches!(type_chart.get_offensive_coverage(&["Dragon".to_string()], 1), Err(TrackerError::UnknownType(_))));
+    }
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::type_chart::{Effectiveness, TypeChart};
+
+/// When and how a matchup was learned, one of these is written in the discovery log for every matchup change
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct Discovery {
+    /// When the matchup was changed
+    pub timestamp: DateTime<Local>,
+    /// Name of the play session the matchup was learned in
+    pub session: String,
+    /// Attacking type of the matchup
+    pub attacking_type: String,
+    /// Defending type of the matchup
+    pub defending_type: String,
+    /// Effectiveness before the change
+    pub previous: Effectiveness,
+    /// Effectiveness after the change
+    pub new: Effectiveness,
+    /// Where or how the matchup was learned, e.g. "Route 110, wild Zigzagoon"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl TypeChart {
+    pub(crate) fn record_discovery(&mut self, type_name: &String, opposing_type_name: &String, previous: Effectiveness, new: Effectiveness, note: Option<String>) {
+        self.discoveries.push(Discovery {
+            timestamp: Local::now(),
+            session: self.session.clone(),
+            attacking_type: type_name.clone(),
+            defending_type: opposing_type_name.clone(),
+            previous,
+            new,
+            note,
+        });
+    }
+
+    // The matchups of a renamed type are logged again under its new name, so that they can still be looked up and replayed
+    pub(crate) fn record_renamed_type(&mut self, type_name: &String, previous_name: &String) {
+        let mut matchups = Vec::new();
+        for opposing_type in &self.type_list {
+            matchups.push((type_name.clone(), opposing_type.clone()));
+            if opposing_type != type_name {
+                matchups.push((opposing_type.clone(), type_name.clone()));
+            }
+        }
+        for (attacking_type, defending_type) in matchups {
+            let effectiveness = self.get_effectiveness(&attacking_type, &defending_type).unwrap_or(Effectiveness::Unknown);
+            if effectiveness != Effectiveness::Unknown {
+                self.record_discovery(&attacking_type, &defending_type, Effectiveness::Unknown, effectiveness, Some(format!("Renamed from {}", previous_name)));
+            }
+        }
+    }
+
+    /// Returns the name of the session the matchups learned from now on are logged with
+    pub fn get_session(&self) -> String {
+        return self.session.clone();
+    }
+
+    /// Names the session the matchups learned from now on are logged with, it defaults to when the type chart was loaded
+    pub fn set_session(&mut self, session: &String) {
+        self.session = session.clone();
+    }
+
+    /// Returns the matchups learned since the type chart was loaded, they are added to the discovery log when it is saved
+    pub fn get_discoveries(&self) -> Vec<Discovery> {
+        return self.discoveries.clone();
+    }
+}
@@ -0,0 +1,322 @@
+use std::{fmt, ops::Mul, str::FromStr};
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer, de};
+
+use crate::error::TrackerError;
+
+/// Damage multiplier of a matchup
+///
+/// Every multiplier in the game is either 0 or a power of 2, so we store the exponent
+/// instead of a float to keep comparisons and combinations exact
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Multiplier {
+    /// The move does no damage
+    Immune,
+    /// The damage is multiplied by 2 to the power of the exponent
+    PowerOfTwo(i8),
+}
+
+/// Effectiveness of a single type against another, as recorded in the type chart
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Effectiveness {
+    /// Confirmed in game
+    Known(Multiplier),
+    /// What we believe the multiplier is, but haven't confirmed yet
+    Suspected(Multiplier),
+    /// Nothing is known about the matchup yet
+    Unknown,
+}
+
+/// Result of combining the effectivenesses against several types, unknown matchups are counted as neutral
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct CombinedEffectiveness {
+    /// Product of the known and suspected multipliers
+    pub multiplier: Multiplier,
+    /// Number of combined matchups that are unknown
+    pub unknown_count: usize,
+    /// Number of combined matchups that are only suspected
+    pub suspected_count: usize,
+}
+
+// Written after the multiplier of suspected matchups, in files and queries
+const SUSPECTED_SUFFIX: &str = " (suspected)";
+
+impl Multiplier {
+    /// 0x
+    pub const IMMUNE: Multiplier = Multiplier::Immune;
+    /// 0.5x
+    pub const NOT_VERY_EFFECTIVE: Multiplier = Multiplier::PowerOfTwo(-1);
+    /// 1x
+    pub const NEUTRAL: Multiplier = Multiplier::PowerOfTwo(0);
+    /// 2x
+    pub const SUPER_EFFECTIVE: Multiplier = Multiplier::PowerOfTwo(1);
+
+    /// Returns the multiplier as a number, for display purposes
+    pub fn as_f32(&self) -> f32 {
+        match self {
+            Multiplier::Immune => 0.,
+            Multiplier::PowerOfTwo(exponent) => 2f32.powi(*exponent as i32),
+        }
+    }
+}
+
+impl Mul for Multiplier {
+    type Output = Multiplier;
+
+    // Multiplying powers of two is adding their exponents
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn mul(self, rhs: Multiplier) -> Multiplier {
+        match (self, rhs) {
+            (Multiplier::PowerOfTwo(lhs), Multiplier::PowerOfTwo(rhs)) => Multiplier::PowerOfTwo(lhs.saturating_add(rhs)),
+            _ => Multiplier::Immune,
+        }
+    }
+}
+
+// Types can stack, so effectivenesses can be multiplied any number of times,
+// after the last prefix the number of stacks is written instead
+static STACK_PREFIXES: [&str; 8] = ["", "Double ", "Triple ", "Quadruple ", "Quintuple ", "Sextuple ", "Septuple ", "Octuple "];
+
+impl fmt::Display for Multiplier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let exponent = match self {
+            Multiplier::Immune => return write!(f, "Immune"),
+            Multiplier::PowerOfTwo(0) => return write!(f, "Neutral"),
+            Multiplier::PowerOfTwo(exponent) => *exponent,
+        };
+        let label = if exponent > 0 { "Super Effective" } else { "Not Very Effective" };
+        let stacks = exponent.unsigned_abs() as usize;
+        match STACK_PREFIXES.get(stacks - 1) {
+            None => return write!(f, "{}x {}", stacks, label),
+            Some(prefix) => return write!(f, "{}{}", prefix, label),
+        }
+    }
+}
+
+impl FromStr for Multiplier {
+    type Err = TrackerError;
+
+    fn from_str(multiplier: &str) -> Result<Multiplier, TrackerError> {
+        let multiplier = multiplier.trim();
+        match multiplier {
+            "Immune" => return Ok(Multiplier::Immune),
+            "Neutral" => return Ok(Multiplier::NEUTRAL),
+            _ => (),
+        }
+        let (stacks, sign) = match (multiplier.strip_suffix("Super Effective"), multiplier.strip_suffix("Not Very Effective")) {
+            (Some(stacks), _) => (stacks, 1),
+            (_, Some(stacks)) => (stacks, -1),
+            _ => return Err(TrackerError::UnknownEffectiveness(multiplier.to_string())),
+        };
+        let nb_stacks = match STACK_PREFIXES.iter().position(|prefix| *prefix == stacks) {
+            Some(position) => Some(position as i16 + 1),
+            None => stacks.strip_suffix("x ").and_then(|nb_stacks| nb_stacks.parse().ok()),
+        };
+        // The sign is applied before narrowing, -128 is written "128x Not Very Effective"
+        match nb_stacks.filter(|nb_stacks: &i16| *nb_stacks > 0).and_then(|nb_stacks| i8::try_from(sign * nb_stacks).ok()) {
+            Some(exponent) => return Ok(Multiplier::PowerOfTwo(exponent)),
+            None => return Err(TrackerError::UnknownEffectiveness(multiplier.to_string())),
+        }
+    }
+}
+
+impl Effectiveness {
+    /// Confirmed immunity
+    pub const IMMUNE: Effectiveness = Effectiveness::Known(Multiplier::IMMUNE);
+    /// Confirmed resistance
+    pub const NOT_VERY_EFFECTIVE: Effectiveness = Effectiveness::Known(Multiplier::NOT_VERY_EFFECTIVE);
+    /// Confirmed neutral matchup
+    pub const NEUTRAL: Effectiveness = Effectiveness::Known(Multiplier::NEUTRAL);
+    /// Confirmed weakness
+    pub const SUPER_EFFECTIVE: Effectiveness = Effectiveness::Known(Multiplier::SUPER_EFFECTIVE);
+
+    /// Returns true if the matchup has been confirmed
+    pub fn is_known(&self) -> bool {
+        return matches!(self, Effectiveness::Known(_));
+    }
+
+    /// Parses an effectiveness written by hand, on top of the names used by [`FromStr`] this accepts
+    /// any case, abbreviations (`SE`, `NVE`, `N`, `Imm`) and multipliers (`2`, `0.5x`, `1/2`, `½`, `x0`)
+    ///
+    /// A `?` after the effectiveness marks it as suspected, and a lone `?` is unknown
+    pub fn parse_lenient(effectiveness: &str) -> Result<Effectiveness, TrackerError> {
+        let effectiveness = effectiveness.trim();
+        if effectiveness == "?" {
+            return Ok(Effectiveness::Unknown);
+        }
+        if let Ok(effectiveness) = effectiveness.parse() {
+            return Ok(effectiveness);
+        }
+        if let Some(suspected_effectiveness) = effectiveness.strip_suffix('?') {
+            match Effectiveness::parse_lenient(suspected_effectiveness)? {
+                Effectiveness::Known(multiplier) => return Ok(Effectiveness::Suspected(multiplier)),
+                _ => return Err(TrackerError::UnknownEffectiveness(effectiveness.to_string())),
+            }
+        }
+        let lowercase_effectiveness = effectiveness.to_lowercase();
+        let multiplier = match lowercase_effectiveness.as_str() {
+            "se" | "super" => Some(Multiplier::SUPER_EFFECTIVE),
+            "nve" | "not very" => Some(Multiplier::NOT_VERY_EFFECTIVE),
+            "n" | "neu" => Some(Multiplier::NEUTRAL),
+            "imm" | "immune" | "no effect" => Some(Multiplier::Immune),
+            "½" => Some(Multiplier::PowerOfTwo(-1)),
+            "¼" => Some(Multiplier::PowerOfTwo(-2)),
+            "⅛" => Some(Multiplier::PowerOfTwo(-3)),
+            lowercase_effectiveness => parse_multiplier_number(lowercase_effectiveness).or_else(|| capitalize_words(lowercase_effectiveness).parse().ok()),
+        };
+        match multiplier {
+            None => return Err(TrackerError::UnknownEffectiveness(effectiveness.to_string())),
+            Some(multiplier) => return Ok(Effectiveness::Known(multiplier)),
+        }
+    }
+
+    /// Returns the confirmed or suspected multiplier of the matchup
+    pub fn multiplier(&self) -> Option<Multiplier> {
+        match self {
+            Effectiveness::Known(multiplier) | Effectiveness::Suspected(multiplier) => return Some(*multiplier),
+            Effectiveness::Unknown => return None,
+        }
+    }
+}
+
+impl fmt::Display for Effectiveness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Effectiveness::Known(multiplier) => write!(f, "{}", multiplier),
+            Effectiveness::Suspected(multiplier) => write!(f, "{}{}", multiplier, SUSPECTED_SUFFIX),
+            Effectiveness::Unknown => write!(f, "?"),
+        }
+    }
+}
+
+impl FromStr for Effectiveness {
+    type Err = TrackerError;
+
+    fn from_str(effectiveness: &str) -> Result<Effectiveness, TrackerError> {
+        let effectiveness = effectiveness.trim();
+        if effectiveness == "?" {
+            return Ok(Effectiveness::Unknown);
+        }
+        if let Some(multiplier) = effectiveness.strip_suffix(SUSPECTED_SUFFIX) {
+            return Ok(Effectiveness::Suspected(multiplier.parse()?));
+        }
+        return Ok(Effectiveness::Known(effectiveness.parse()?));
+    }
+}
+
+// Reads multipliers written as numbers, like 2, 0.5x, x0.25 or 1/2
+fn parse_multiplier_number(number: &str) -> Option<Multiplier> {
+    let number = number.trim_start_matches('x').trim_end_matches('x').trim();
+    let value: f32 = match number.split_once('/') {
+        Some((numerator, denominator)) => numerator.trim().parse::<f32>().ok()? / denominator.trim().parse::<f32>().ok()?,
+        None => number.parse().ok()?,
+    };
+    if value == 0. {
+        return Some(Multiplier::Immune);
+    }
+    let exponent = value.log2();
+    // Only powers of two can be multipliers
+    if !exponent.is_finite() || exponent.fract() != 0. || exponent.abs() > i8::MAX as f32 {
+        return None;
+    }
+    return Some(Multiplier::PowerOfTwo(exponent as i8));
+}
+
+fn capitalize_words(text: &str) -> String {
+    return text.split_whitespace()
+        .map(|word| {
+            let mut characters = word.chars();
+            match characters.next() {
+                None => String::new(),
+                Some(first) => first.to_uppercase().chain(characters).collect(),
+            }
+        })
+        .collect::<Vec<String>>()
+        .join(" ");
+}
+
+// Saved with the same names as the ones displayed, so chart files stay readable
+impl Serialize for Effectiveness {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        return serializer.collect_str(self);
+    }
+}
+
+impl<'de> Deserialize<'de> for Effectiveness {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Effectiveness, D::Error> {
+        let effectiveness = String::deserialize(deserializer)?;
+        return effectiveness.parse().map_err(de::Error::custom);
+    }
+}
+
+impl CombinedEffectiveness {
+    /// Effectiveness against a single known type
+    pub fn new(multiplier: Multiplier) -> CombinedEffectiveness {
+        return CombinedEffectiveness { multiplier, unknown_count: 0, suspected_count: 0 };
+    }
+
+    /// Combines the effectivenesses against every type, unknown ones count as neutral
+    pub fn combine<'a, I: IntoIterator<Item = &'a Effectiveness>>(effectivenesses: I) -> CombinedEffectiveness {
+        let mut combined_effectiveness = CombinedEffectiveness::new(Multiplier::NEUTRAL);
+        let mut confirmed_immune = false;
+        for effectiveness in effectivenesses {
+            match effectiveness {
+                Effectiveness::Known(multiplier) => {
+                    confirmed_immune |= *multiplier == Multiplier::Immune;
+                    combined_effectiveness.multiplier = combined_effectiveness.multiplier * *multiplier;
+                },
+                Effectiveness::Suspected(multiplier) => {
+                    combined_effectiveness.suspected_count += 1;
+                    combined_effectiveness.multiplier = combined_effectiveness.multiplier * *multiplier;
+                },
+                Effectiveness::Unknown => combined_effectiveness.unknown_count += 1,
+            }
+        }
+        // A confirmed immunity stays immune no matter what the other effectivenesses are
+        if confirmed_immune {
+            combined_effectiveness.unknown_count = 0;
+            combined_effectiveness.suspected_count = 0;
+        }
+        return combined_effectiveness;
+    }
+
+    /// Returns true if the combined multiplier is 1x
+    pub fn is_neutral(&self) -> bool {
+        return self.multiplier == Multiplier::NEUTRAL;
+    }
+
+    /// Returns true if at least one of the combined matchups is unknown
+    pub fn is_unsure(&self) -> bool {
+        return self.unknown_count > 0;
+    }
+
+    /// Returns true if at least one of the combined matchups is only suspected
+    pub fn is_suspected(&self) -> bool {
+        return self.suspected_count > 0;
+    }
+}
+
+impl fmt::Display for CombinedEffectiveness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.multiplier, "?".repeat(self.unknown_count))?;
+        if self.is_suspected() {
+            write!(f, "{}", SUSPECTED_SUFFIX)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_multiplier_reads_back_as_written() {
+        for exponent in i8::MIN..=i8::MAX {
+            let multiplier = Multiplier::PowerOfTwo(exponent);
+            assert_eq!(multiplier.to_string().parse::<Multiplier>().unwrap(), multiplier);
+        }
+        assert_eq!(Multiplier::Immune.to_string().parse::<Multiplier>().unwrap(), Multiplier::Immune);
+        assert!("128x Super Effective".parse::<Multiplier>().is_err());
+    }
+}
@@ -0,0 +1,411 @@
+use std::{collections::BTreeMap, fmt::Display, str::FromStr};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{error::TrackerError, type_chart::{Edit, Effectiveness, Multiplier, TypeChart}};
+
+// Attacking and defending type of a matchup
+type Cell = (String, String);
+
+// Effectivenesses a single type can have against another one
+const SINGLE_TYPE_MULTIPLIERS: [Multiplier; 4] = [Multiplier::IMMUNE, Multiplier::NOT_VERY_EFFECTIVE, Multiplier::NEUTRAL, Multiplier::SUPER_EFFECTIVE];
+
+/// What the game says after a move hits, which only tells on which side of neutral the combined multiplier is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BattleMessage {
+    /// "It's super effective!"
+    SuperEffective,
+    /// The game doesn't say anything
+    Neutral,
+    /// "It's not very effective..."
+    NotVeryEffective,
+    /// "It doesn't affect the Pokemon..."
+    NoEffect,
+}
+
+impl BattleMessage {
+    /// Message the game shows for a move with this combined multiplier
+    pub fn for_multiplier(multiplier: Multiplier) -> BattleMessage {
+        match multiplier {
+            Multiplier::Immune => return BattleMessage::NoEffect,
+            multiplier if multiplier > Multiplier::NEUTRAL => return BattleMessage::SuperEffective,
+            multiplier if multiplier < Multiplier::NEUTRAL => return BattleMessage::NotVeryEffective,
+            _ => return BattleMessage::Neutral,
+        }
+    }
+}
+
+impl Display for BattleMessage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BattleMessage::SuperEffective => return write!(f, "It's super effective!"),
+            BattleMessage::Neutral => return write!(f, "No message"),
+            BattleMessage::NotVeryEffective => return write!(f, "It's not very effective..."),
+            BattleMessage::NoEffect => return write!(f, "It doesn't affect the Pokemon..."),
+        }
+    }
+}
+
+// Accepts the messages as written in game as well as the same shortcuts as effectivenesses
+impl FromStr for BattleMessage {
+    type Err = TrackerError;
+
+    fn from_str(message: &str) -> Result<BattleMessage, TrackerError> {
+        let simplified_message: String = message.trim().to_lowercase().chars().filter(|character| !"!.'’".contains(*character)).collect();
+        match simplified_message.as_str() {
+            "1" | "se" | "super" | "super effective" | "its super effective" => return Ok(BattleMessage::SuperEffective),
+            "2" | "neutral" | "none" | "no message" => return Ok(BattleMessage::Neutral),
+            "3" | "nve" | "not very effective" | "its not very effective" => return Ok(BattleMessage::NotVeryEffective),
+            "4" | "immune" | "no effect" | "doesnt affect" | "it doesnt affect" | "it doesnt affect the pokemon" => return Ok(BattleMessage::NoEffect),
+            _ => return Err(TrackerError::UnknownBattleMessage(message.trim().to_string())),
+        }
+    }
+}
+
+/// A move of `attacking_type` hitting a Pokemon with all of `defending_types`, and what the game said
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Observation {
+    /// Type of the move
+    pub attacking_type: String,
+    /// Types of the Pokemon that was hit
+    pub defending_types: Vec<String>,
+    /// What the game said
+    pub message: BattleMessage,
+}
+
+impl Display for Observation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} against {}: {}", self.attacking_type, self.defending_types.join("/"), self.message);
+    }
+}
+
+/// A matchup only one effectiveness fits, given the observations and the confirmed matchups
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Deduction {
+    /// Attacking type of the matchup
+    pub attacking_type: String,
+    /// Defending type of the matchup
+    pub defending_type: String,
+    /// Effectiveness currently recorded for the matchup
+    pub previous: Effectiveness,
+    /// The only multiplier that fits
+    pub deduced: Multiplier,
+}
+
+impl Display for Deduction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return write!(f, "{} against {}: {} -> {}", self.attacking_type, self.defending_type, self.previous, self.deduced);
+    }
+}
+
+/// Observations that can't all be right
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Contradiction {
+    /// Observations involved, in the order they were recorded
+    pub observations: Vec<Observation>,
+    /// Matchup no effectiveness fits anymore, missing when a single observation disagrees with the confirmed matchups
+    pub matchup: Option<(String, String)>,
+}
+
+impl Display for Contradiction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let observations: Vec<String> = self.observations.iter().map(|observation| observation.to_string()).collect();
+        match &self.matchup {
+            None => return write!(f, "{} disagrees with the confirmed matchups", observations.join(", ")),
+            Some((attacking_type, defending_type)) => {
+                return write!(f, "No effectiveness of {} against {} fits all of {}", attacking_type, defending_type, observations.join(" | "));
+            },
+        }
+    }
+}
+
+/// What the battle observations tell about the type chart, see [`TypeChart::infer_matchups`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Inference {
+    /// Matchups that can be filled in
+    pub deductions: Vec<Deduction>,
+    /// Observations to check again
+    pub contradictions: Vec<Contradiction>,
+}
+
+// Adds to `supported` every multiplier that is part of a way to get the message of the observation
+fn collect_supported_multipliers(message: BattleMessage, domains: &[&Vec<Multiplier>], assignment: &mut Vec<Multiplier>, supported: &mut [Vec<Multiplier>]) {
+    if assignment.len() == domains.len() {
+        let multiplier = assignment.iter().fold(Multiplier::NEUTRAL, |multiplier, current_multiplier| multiplier * *current_multiplier);
+        if BattleMessage::for_multiplier(multiplier) != message {
+            return;
+        }
+        for (supported_multipliers, current_multiplier) in supported.iter_mut().zip(assignment.iter()) {
+            if !supported_multipliers.contains(current_multiplier) {
+                supported_multipliers.push(*current_multiplier);
+            }
+        }
+        return;
+    }
+    for multiplier in domains[assignment.len()] {
+        assignment.push(*multiplier);
+        collect_supported_multipliers(message, domains, assignment, supported);
+        assignment.pop();
+    }
+}
+
+// For every defending type of the observation, the multipliers it can still have for the message to be right
+fn supported_multipliers(observation: &Observation, domains: &BTreeMap<Cell, Vec<Multiplier>>) -> Vec<Vec<Multiplier>> {
+    let cell_domains: Vec<&Vec<Multiplier>> = observation.defending_types.iter()
+        .map(|defending_type| &domains[&(observation.attacking_type.clone(), defending_type.clone())])
+        .collect();
+    let mut supported = vec![Vec::new(); cell_domains.len()];
+    collect_supported_multipliers(observation.message, &cell_domains, &mut Vec::new(), &mut supported);
+    return supported;
+}
+
+impl TypeChart {
+    /// Records what the game said when `attacking_type` hit a Pokemon with all of `defending_types`
+    pub fn add_observation(&mut self, attacking_type: &String, defending_types: &[String], message: BattleMessage) -> Result<(), TrackerError> {
+        let mut unique_defending_types: Vec<String> = Vec::new();
+        for type_name in std::iter::once(attacking_type).chain(defending_types) {
+            if !self.type_list.contains(type_name) {
+                return Err(TrackerError::UnknownType(type_name.clone()));
+            }
+        }
+        for defending_type in defending_types {
+            if !unique_defending_types.contains(defending_type) {
+                unique_defending_types.push(defending_type.clone());
+            }
+        }
+        if unique_defending_types.is_empty() {
+            return Ok(());
+        }
+        let observation = Observation { attacking_type: attacking_type.clone(), defending_types: unique_defending_types, message };
+        self.commit_edit(Edit::AddObservation { observation });
+        return Ok(());
+    }
+
+    /// Returns every battle observation, from the oldest to the most recent
+    pub fn get_observations(&self) -> Vec<Observation> {
+        return self.observations.clone();
+    }
+
+    // Edits giving the observations with `type_name` `new_type_name` instead, or forgetting them without one,
+    // since what the game said can't be split between the types of the defender
+    pub(crate) fn observation_type_edits(&self, type_name: &String, new_type_name: Option<&String>) -> Vec<Edit> {
+        let mut edits = Vec::new();
+        for (position, observation) in self.observations.iter().enumerate().rev() {
+            if observation.attacking_type != *type_name && !observation.defending_types.contains(type_name) {
+                continue;
+            }
+            let new = new_type_name.map(|new_type_name| {
+                let rename = |current_type: &String| if current_type == type_name { new_type_name.clone() } else { current_type.clone() };
+                let mut defending_types: Vec<String> = Vec::new();
+                for defending_type in observation.defending_types.iter().map(rename) {
+                    if !defending_types.contains(&defending_type) {
+                        defending_types.push(defending_type);
+                    }
+                }
+                return Observation { attacking_type: rename(&observation.attacking_type), defending_types, message: observation.message };
+            });
+            edits.push(Edit::SetObservation { position, previous: Some(observation.clone()), new });
+        }
+        return edits;
+    }
+
+    /// Finds the unknown or suspected matchups the battle observations leave a single effectiveness for,
+    /// and the observations that contradict each other or the confirmed matchups
+    ///
+    /// Nothing is deduced when the observations contradict each other, since one of them has to be wrong.
+    /// Observations about types that were removed since are ignored
+    pub fn infer_matchups(&self) -> Inference {
+        let mut inference = Inference::default();
+        let observations: Vec<&Observation> = self.observations.iter()
+            .filter(|observation| self.type_list.contains(&observation.attacking_type))
+            .filter(|observation| observation.defending_types.iter().all(|defending_type| self.type_list.contains(defending_type)))
+            .collect();
+        let mut domains: BTreeMap<Cell, Vec<Multiplier>> = BTreeMap::new();
+        for observation in &observations {
+            for defending_type in &observation.defending_types {
+                let cell = (observation.attacking_type.clone(), defending_type.clone());
+                let domain = match self.get_effectiveness(&cell.0, &cell.1) {
+                    Ok(Effectiveness::Known(multiplier)) => vec![multiplier],
+                    _ => SINGLE_TYPE_MULTIPLIERS.to_vec(),
+                };
+                domains.insert(cell, domain);
+            }
+        }
+
+        // An observation that doesn't fit the confirmed matchups is wrong on its own, it is left out of the deductions
+        let mut consistent_observations = Vec::new();
+        for observation in observations {
+            if supported_multipliers(observation, &domains).iter().any(Vec::is_empty) {
+                inference.contradictions.push(Contradiction { observations: vec![observation.clone()], matchup: None });
+            } else {
+                consistent_observations.push(observation);
+            }
+        }
+
+        // Every observation removes the multipliers it rules out, until none of them can remove anything more
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for observation in &consistent_observations {
+                let supported = supported_multipliers(observation, &domains);
+                for (defending_type, supported_multipliers) in observation.defending_types.iter().zip(supported) {
+                    let cell = (observation.attacking_type.clone(), defending_type.clone());
+                    let domain = domains.get_mut(&cell).expect("Every matchup of the observations has a domain");
+                    if supported_multipliers.len() < domain.len() {
+                        domain.retain(|multiplier| supported_multipliers.contains(multiplier));
+                        changed = true;
+                    }
+                    if domain.is_empty() {
+                        let observations = consistent_observations.iter()
+                            .filter(|observation| observation.attacking_type == cell.0 && observation.defending_types.contains(&cell.1))
+                            .map(|observation| (*observation).clone())
+                            .collect();
+                        inference.contradictions.push(Contradiction { observations, matchup: Some(cell) });
+                        return inference;
+                    }
+                }
+            }
+        }
+
+        for ((attacking_type, defending_type), domain) in domains {
+            let previous = self.get_effectiveness(&attacking_type, &defending_type).unwrap_or(Effectiveness::Unknown);
+            if let [deduced] = domain[..] && previous != Effectiveness::Known(deduced) {
+                inference.deductions.push(Deduction { attacking_type, defending_type, previous, deduced });
+            }
+        }
+        return inference;
+    }
+
+    /// Confirms the deduced matchups, they are logged as deduced from the battle observations
+    pub fn apply_deductions(&mut self, deductions: &[Deduction]) -> Result<(), TrackerError> {
+        for deduction in deductions {
+            let note = Some("Deduced from battle observations".to_string());
+            self.add_effectiveness_with_note(&deduction.attacking_type, &deduction.defending_type, Effectiveness::Known(deduction.deduced), note)?;
+        }
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::type_chart_with;
+
+    fn observe(type_chart: &mut TypeChart, attacking_type: &str, defending_types: &[&str], message: BattleMessage) {
+        let defending_types: Vec<String> = defending_types.iter().map(|defending_type| defending_type.to_string()).collect();
+        type_chart.add_observation(&attacking_type.to_string(), &defending_types, message).unwrap();
+    }
+
+    #[test]
+    fn dual_type_observation_forces_the_unknown_matchup() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass", "Water"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Water".to_string(), Effectiveness::NEUTRAL).unwrap();
+        observe(&mut type_chart, "Fire", &["Grass", "Water"], BattleMessage::SuperEffective);
+        let inference = type_chart.infer_matchups();
+        assert!(inference.contradictions.is_empty());
+        assert_eq!(inference.deductions, vec![Deduction {
+            attacking_type: "Fire".to_string(),
+            defending_type: "Grass".to_string(),
+            previous: Effectiveness::Unknown,
+            deduced: Multiplier::SUPER_EFFECTIVE,
+        }]);
+
+        type_chart.apply_deductions(&inference.deductions).unwrap();
+        assert_eq!(type_chart.get_effectiveness(&"Fire".to_string(), &"Grass".to_string()).unwrap(), Effectiveness::SUPER_EFFECTIVE);
+        assert!(type_chart.infer_matchups().deductions.is_empty());
+    }
+
+    #[test]
+    fn ambiguous_observation_deduces_nothing() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass", "Steel"]);
+        observe(&mut type_chart, "Fire", &["Grass", "Steel"], BattleMessage::SuperEffective);
+        let inference = type_chart.infer_matchups();
+        assert!(inference.deductions.is_empty());
+        assert!(inference.contradictions.is_empty());
+    }
+
+    #[test]
+    fn observations_that_disagree_are_a_contradiction() {
+        let mut type_chart = type_chart_with(&["Fire", "Water"]);
+        observe(&mut type_chart, "Fire", &["Water"], BattleMessage::SuperEffective);
+        observe(&mut type_chart, "Fire", &["Water"], BattleMessage::NotVeryEffective);
+        let inference = type_chart.infer_matchups();
+        // One of them is wrong, so nothing is deduced
+        assert!(inference.deductions.is_empty());
+        assert_eq!(inference.contradictions.len(), 1);
+        assert_eq!(inference.contradictions[0].matchup, Some(("Fire".to_string(), "Water".to_string())));
+        assert_eq!(inference.contradictions[0].observations.len(), 2);
+    }
+
+    #[test]
+    fn observation_against_a_confirmed_matchup_is_a_contradiction() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass", "Water"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Water".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        observe(&mut type_chart, "Fire", &["Water"], BattleMessage::NotVeryEffective);
+        observe(&mut type_chart, "Fire", &["Grass"], BattleMessage::NoEffect);
+        let inference = type_chart.infer_matchups();
+        assert_eq!(inference.contradictions.len(), 1);
+        assert_eq!(inference.contradictions[0].matchup, None);
+        assert_eq!(inference.contradictions[0].observations[0].defending_types, vec!["Water".to_string()]);
+        // The other observation is still used
+        assert_eq!(inference.deductions.len(), 1);
+        assert_eq!(inference.deductions[0].deduced, Multiplier::IMMUNE);
+    }
+
+    #[test]
+    fn observations_about_removed_types_are_ignored() {
+        let mut type_chart = type_chart_with(&["Fire", "Water"]);
+        // Written by an older version of the tracker, which kept the observations of removed types
+        type_chart.observations.push(Observation {
+            attacking_type: "Fire".to_string(),
+            defending_types: vec!["Water".to_string(), "Ice".to_string()],
+            message: BattleMessage::NoEffect,
+        });
+        let inference = type_chart.infer_matchups();
+        assert!(inference.deductions.is_empty());
+        assert!(inference.contradictions.is_empty());
+    }
+
+    #[test]
+    fn removing_a_type_forgets_its_observations() {
+        let mut type_chart = type_chart_with(&["Fire", "Water", "Grass"]);
+        observe(&mut type_chart, "Fire", &["Water"], BattleMessage::NotVeryEffective);
+        observe(&mut type_chart, "Fire", &["Grass"], BattleMessage::SuperEffective);
+        type_chart.remove_existing_type(&"Water".to_string()).unwrap();
+        type_chart.add_new_type(&"Water".to_string()).unwrap();
+        assert_eq!(type_chart.get_observations().len(), 1);
+        let inference = type_chart.infer_matchups();
+        assert!(inference.deductions.iter().all(|deduction| deduction.defending_type != "Water"));
+
+        // Undoing the addition then the removal brings the observation back
+        type_chart.undo();
+        type_chart.undo();
+        assert_eq!(type_chart.get_observations().len(), 2);
+        assert_eq!(type_chart.get_observations()[0].defending_types, vec!["Water".to_string()]);
+    }
+
+    #[test]
+    fn merging_types_renames_them_in_the_observations() {
+        let mut type_chart = type_chart_with(&["Fire", "Fier", "Grass"]);
+        observe(&mut type_chart, "Fier", &["Grass"], BattleMessage::SuperEffective);
+        observe(&mut type_chart, "Grass", &["Fire", "Fier"], BattleMessage::NotVeryEffective);
+        type_chart.merge_types(&"Fire".to_string(), &"Fier".to_string(), &[]).unwrap();
+        let observations = type_chart.get_observations();
+        assert_eq!(observations[0].attacking_type, "Fire");
+        assert_eq!(observations[1].defending_types, vec!["Fire".to_string()]);
+
+        type_chart.undo();
+        assert_eq!(type_chart.get_observations()[0].attacking_type, "Fier");
+        assert_eq!(type_chart.get_observations()[1].defending_types, vec!["Fire".to_string(), "Fier".to_string()]);
+    }
+
+    #[test]
+    fn battle_messages_are_read_as_written_in_game() {
+        assert_eq!("It's super effective!".parse::<BattleMessage>().unwrap(), BattleMessage::SuperEffective);
+        assert_eq!("no message".parse::<BattleMessage>().unwrap(), BattleMessage::Neutral);
+        assert_eq!("It doesn't affect the Pokemon...".parse::<BattleMessage>().unwrap(), BattleMessage::NoEffect);
+        // Normal is a type name
+        assert!("normal".parse::<BattleMessage>().is_err());
+    }
+}
@@ -0,0 +1,334 @@
+use std::{collections::BTreeMap, fmt::Display};
+
+use chrono::{DateTime, Local};
+use serde::{Deserialize, Serialize};
+
+use crate::type_chart::{Effectiveness, Observation, TypeChart};
+
+/// Number of changes kept in the history of a type chart, the oldest ones can't be undone anymore
+pub const MAX_HISTORY_LENGTH: usize = 100;
+
+/// A change made to a type chart, with everything needed to revert it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Edit {
+    /// A new type, added after the others
+    AddType {
+        /// Name of the new type
+        type_name: String,
+    },
+    /// Keeps the position of the type and its known matchups to put them back
+    RemoveType {
+        /// Name of the removed type
+        type_name: String,
+        /// Position the type had in the type list
+        position: usize,
+        /// Known matchups of the type attacking the others
+        attacking: BTreeMap<String, Effectiveness>,
+        /// Known matchups of the others attacking the type
+        defending: BTreeMap<String, Effectiveness>,
+        /// Changes to the species and observations that had the type, made before removing it
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        edits: Vec<Edit>,
+    },
+    /// A type given another name, everything it was part of follows it
+    RenameType {
+        /// Name before the change
+        previous: String,
+        /// Name after the change
+        new: String,
+    },
+    /// A matchup set to another effectiveness
+    SetEffectiveness {
+        /// Attacking type of the matchup
+        attacking_type: String,
+        /// Defending type of the matchup
+        defending_type: String,
+        /// Effectiveness before the change
+        previous: Effectiveness,
+        /// Effectiveness after the change
+        new: Effectiveness,
+    },
+    /// A metadata value set, changed or removed
+    SetMetadata {
+        /// Name of the value
+        key: String,
+        /// Value before the change, missing if there was none
+        previous: Option<String>,
+        /// Value after the change, missing if it was removed
+        new: Option<String>,
+    },
+    /// The changes of the merge are made in order, and reverted in the opposite order
+    MergeTypes {
+        /// Type that is kept
+        type_name: String,
+        /// Type that is merged into the kept one and removed
+        merged_type_name: String,
+        /// Changes the merge is made of
+        edits: Vec<Edit>,
+    },
+    /// A battle observation recorded after the others
+    AddObservation {
+        /// The new observation
+        observation: Observation,
+    },
+    /// A species given other types, added or removed
+    SetSpecies {
+        /// Name of the species
+        species_name: String,
+        /// Types before the change, missing if the species was new
+        previous: Option<Vec<String>>,
+        /// Types after the change, missing if the species was removed
+        new: Option<Vec<String>>,
+    },
+    /// Observations are removed from the last to the first, so that the positions stay right when undoing
+    SetObservation {
+        /// Position of the observation in the list
+        position: usize,
+        /// Observation before the change, missing if it was inserted
+        previous: Option<Observation>,
+        /// Observation after the change, missing if it was removed
+        new: Option<Observation>,
+    },
+    /// Types and species read from a file at once, undone together like a merge
+    ImportSpecies {
+        /// Where the types and species come from
+        source: String,
+        /// Changes the import is made of
+        edits: Vec<Edit>,
+    },
+}
+
+impl Display for Edit {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Edit::AddType { type_name } => return write!(f, "Added type {}", type_name),
+            Edit::RemoveType { type_name, .. } => return write!(f, "Removed type {}", type_name),
+            Edit::RenameType { previous, new } => return write!(f, "Renamed type {} to {}", previous, new),
+            Edit::SetEffectiveness { attacking_type, defending_type, previous, new } => {
+                return write!(f, "{} against {}: {} -> {}", attacking_type, defending_type, previous, new);
+            },
+            Edit::SetMetadata { key, previous, new } => {
+                return write!(f, "Metadata {}: {} -> {}", key, previous.as_deref().unwrap_or("none"), new.as_deref().unwrap_or("none"));
+            },
+            Edit::MergeTypes { type_name, merged_type_name, .. } => return write!(f, "Merged type {} into {}", merged_type_name, type_name),
+            Edit::AddObservation { observation } => return write!(f, "Observed {}", observation),
+            Edit::SetSpecies { species_name, previous, new } => {
+                let types = |species_types: &Option<Vec<String>>| species_types.as_ref().map(|species_types| species_types.join("/")).unwrap_or("none".to_string());
+                return write!(f, "Species {}: {} -> {}", species_name, types(previous), types(new));
+            },
+            Edit::SetObservation { previous, new, .. } => {
+                let observation = |observation: &Option<Observation>| observation.as_ref().map(|observation| observation.to_string()).unwrap_or("none".to_string());
+                return write!(f, "Observation {} -> {}", observation(previous), observation(new));
+            },
+            Edit::ImportSpecies { source, .. } => return write!(f, "Imported types and species from {}", source),
+        }
+    }
+}
+
+/// A change made to a type chart and when it was made
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// When the change was made
+    pub timestamp: DateTime<Local>,
+    /// The change itself
+    pub edit: Edit,
+}
+
+impl TypeChart {
+    // Makes a change and records it, which forgets the changes that were undone
+    pub(crate) fn commit_edit(&mut self, edit: Edit) {
+        self.apply_edit(&edit);
+        self.history.push(JournalEntry { timestamp: Local::now(), edit });
+        if self.history.len() > MAX_HISTORY_LENGTH {
+            self.history.remove(0);
+        }
+        self.undone_history.clear();
+    }
+
+    // The history can be edited by hand in the chart file, so changes to missing types are ignored instead of failing
+    fn apply_edit(&mut self, edit: &Edit) {
+        match edit {
+            Edit::AddType { type_name } => self.insert_type(type_name, self.type_list.len()),
+            Edit::RemoveType { type_name, edits, .. } => {
+                for edit in edits {
+                    self.apply_edit(edit);
+                }
+                self.delete_type(type_name);
+            },
+            Edit::RenameType { previous, new } => self.replace_type_name(previous, new),
+            Edit::SetEffectiveness { attacking_type, defending_type, new, .. } => self.set_matchup(attacking_type, defending_type, *new),
+            Edit::SetMetadata { key, new, .. } => self.set_metadata_value(key, new),
+            Edit::MergeTypes { edits, .. } | Edit::ImportSpecies { edits, .. } => {
+                for edit in edits {
+                    self.apply_edit(edit);
+                }
+            },
+            Edit::AddObservation { observation } => self.observations.push(observation.clone()),
+            Edit::SetSpecies { species_name, new, .. } => self.set_species_value(species_name, new),
+            Edit::SetObservation { position, previous, new } => self.set_observation_value(*position, previous, new),
+        }
+    }
+
+    fn revert_edit(&mut self, edit: &Edit) {
+        match edit {
+            Edit::AddType { type_name } => self.delete_type(type_name),
+            Edit::RemoveType { type_name, position, attacking, defending, edits } => {
+                self.insert_type(type_name, *position);
+                for (defending_type, effectiveness) in attacking {
+                    self.set_matchup(type_name, defending_type, *effectiveness);
+                }
+                for (attacking_type, effectiveness) in defending {
+                    self.set_matchup(attacking_type, type_name, *effectiveness);
+                }
+                for edit in edits.iter().rev() {
+                    self.revert_edit(edit);
+                }
+            },
+            Edit::RenameType { previous, new } => self.replace_type_name(new, previous),
+            Edit::SetEffectiveness { attacking_type, defending_type, previous, .. } => self.set_matchup(attacking_type, defending_type, *previous),
+            Edit::SetMetadata { key, previous, .. } => self.set_metadata_value(key, previous),
+            Edit::MergeTypes { edits, .. } | Edit::ImportSpecies { edits, .. } => {
+                for edit in edits.iter().rev() {
+                    self.revert_edit(edit);
+                }
+            },
+            Edit::AddObservation { observation } => {
+                if let Some(position) = self.observations.iter().rposition(|current_observation| current_observation == observation) {
+                    self.observations.remove(position);
+                }
+            },
+            Edit::SetSpecies { species_name, previous, .. } => self.set_species_value(species_name, previous),
+            Edit::SetObservation { position, previous, new } => self.set_observation_value(*position, new, previous),
+        }
+    }
+
+    // The discovery log follows every matchup change, so that replaying it gives back the type chart
+    fn record_matchup_changes(&mut self, edit: &Edit, undone: bool) {
+        match edit {
+            Edit::SetEffectiveness { attacking_type, defending_type, previous, new } if undone => {
+                self.record_discovery(attacking_type, defending_type, *new, *previous, Some("Undone".to_string()));
+            },
+            Edit::SetEffectiveness { attacking_type, defending_type, previous, new } => {
+                self.record_discovery(attacking_type, defending_type, *previous, *new, Some("Redone".to_string()));
+            },
+            Edit::RenameType { previous, new } if undone => self.record_renamed_type(previous, new),
+            Edit::RenameType { previous, new } => self.record_renamed_type(new, previous),
+            // A removed type is skipped when replaying, but its matchups are back when the removal is undone
+            Edit::RemoveType { type_name, attacking, defending, .. } if undone => {
+                for (defending_type, effectiveness) in attacking {
+                    self.record_discovery(type_name, defending_type, Effectiveness::Unknown, *effectiveness, Some("Undone".to_string()));
+                }
+                for (attacking_type, effectiveness) in defending {
+                    self.record_discovery(attacking_type, type_name, Effectiveness::Unknown, *effectiveness, Some("Undone".to_string()));
+                }
+            },
+            Edit::MergeTypes { edits, .. } if undone => {
+                for edit in edits.iter().rev() {
+                    self.record_matchup_changes(edit, undone);
+                }
+            },
+            Edit::MergeTypes { edits, .. } => {
+                for edit in edits {
+                    self.record_matchup_changes(edit, undone);
+                }
+            },
+            _ => (),
+        }
+    }
+
+    /// Reverts the last change that wasn't undone yet, and returns it
+    pub fn undo(&mut self) -> Option<JournalEntry> {
+        let entry = self.history.pop()?;
+        self.revert_edit(&entry.edit);
+        self.record_matchup_changes(&entry.edit, true);
+        self.undone_history.push(entry.clone());
+        return Some(entry);
+    }
+
+    /// Makes the last undone change again, and returns it
+    pub fn redo(&mut self) -> Option<JournalEntry> {
+        let entry = self.undone_history.pop()?;
+        self.apply_edit(&entry.edit);
+        self.record_matchup_changes(&entry.edit, false);
+        self.history.push(entry.clone());
+        return Some(entry);
+    }
+
+    /// Returns the changes made to the type chart, from the oldest to the most recent
+    pub fn get_history(&self) -> Vec<JournalEntry> {
+        return self.history.clone();
+    }
+
+    /// Returns the changes that were undone and can be redone, from the next one to redo to the last one
+    pub fn get_undone_history(&self) -> Vec<JournalEntry> {
+        return self.undone_history.iter().rev().cloned().collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::type_chart_with, type_chart::Multiplier};
+
+    fn effectiveness(type_chart: &TypeChart) -> Effectiveness {
+        return type_chart.get_effectiveness(&"Fire".to_string(), &"Grass".to_string()).unwrap();
+    }
+
+    #[test]
+    fn undo_and_redo_go_back_and_forth_in_the_history() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::Suspected(Multiplier::SUPER_EFFECTIVE)).unwrap();
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        assert_eq!(type_chart.get_history().len(), 4);
+
+        let undone_entry = type_chart.undo().unwrap();
+        assert_eq!(undone_entry.edit.to_string(), "Fire against Grass: Super Effective (suspected) -> Super Effective");
+        assert_eq!(effectiveness(&type_chart), Effectiveness::Suspected(Multiplier::SUPER_EFFECTIVE));
+        type_chart.undo();
+        assert_eq!(effectiveness(&type_chart), Effectiveness::Unknown);
+        assert_eq!(type_chart.get_undone_history().len(), 2);
+
+        type_chart.redo();
+        type_chart.redo();
+        assert_eq!(effectiveness(&type_chart), Effectiveness::SUPER_EFFECTIVE);
+        assert!(type_chart.redo().is_none());
+        assert_eq!(type_chart.get_history().len(), 4);
+    }
+
+    #[test]
+    fn undoing_a_removal_puts_the_type_back_in_place() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass", "Water"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.remove_existing_type(&"Grass".to_string()).unwrap();
+        assert_eq!(type_chart.get_type_list(), ["Fire", "Water"].map(String::from));
+        type_chart.undo();
+        assert_eq!(type_chart.get_type_list(), ["Fire", "Grass", "Water"].map(String::from));
+        assert_eq!(effectiveness(&type_chart), Effectiveness::SUPER_EFFECTIVE);
+    }
+
+    #[test]
+    fn a_new_change_forgets_the_undone_ones() {
+        let mut type_chart = type_chart_with(&["Fire", "Grass"]);
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        type_chart.undo();
+        type_chart.add_effectiveness(&"Fire".to_string(), &"Grass".to_string(), Effectiveness::NOT_VERY_EFFECTIVE).unwrap();
+        assert!(type_chart.get_undone_history().is_empty());
+        assert!(type_chart.redo().is_none());
+        assert_eq!(effectiveness(&type_chart), Effectiveness::NOT_VERY_EFFECTIVE);
+    }
+
+    #[test]
+    fn only_the_last_changes_are_kept() {
+        let mut type_chart = TypeChart::empty();
+        for index in 0..MAX_HISTORY_LENGTH + 5 {
+            type_chart.add_new_type(&format!("Type {}", index)).unwrap();
+        }
+        let history = type_chart.get_history();
+        assert_eq!(history.len(), MAX_HISTORY_LENGTH);
+        assert_eq!(history[0].edit, Edit::AddType { type_name: "Type 5".to_string() });
+        while type_chart.undo().is_some() {}
+        // The first types were added too long ago to be undone
+        assert_eq!(type_chart.get_type_list(), (0..5).map(|index| format!("Type {}", index)).collect::<Vec<String>>());
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+
+use crate::{error::TrackerError, type_chart::{Edit, Effectiveness, TypeChart}};
+
+// Attacking and defending type of a matchup
+type Cell = (String, String);
+
+/// A matchup the two merged types disagree on
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// Names after the merge, the merged type is replaced by the type it is merged into
+    pub attacking_type: String,
+    /// Defending type of the matchup, renamed the same way
+    pub defending_type: String,
+    /// Effectiveness recorded for the type that is kept
+    pub kept: Effectiveness,
+    /// Effectiveness recorded for the merged type
+    pub merged: Effectiveness,
+}
+
+// Unknown matchups and suspicions are filled by what the other type knows, only real disagreements are conflicts
+fn combine_matchups(kept: Effectiveness, merged: Effectiveness) -> Option<Effectiveness> {
+    match (kept, merged) {
+        (kept, Effectiveness::Unknown) => return Some(kept),
+        (Effectiveness::Unknown, merged) => return Some(merged),
+        (kept, merged) if kept == merged => return Some(kept),
+        (Effectiveness::Known(_), Effectiveness::Suspected(_)) => return Some(kept),
+        (Effectiveness::Suspected(_), Effectiveness::Known(_)) => return Some(merged),
+        _ => return None,
+    }
+}
+
+impl TypeChart {
+    // Matchups of `type_name` once `merged_type_name` is folded into it, conflicts keep the matchup of `type_name`
+    fn merged_matchups(&self, type_name: &String, merged_type_name: &String) -> Result<(BTreeMap<Cell, Effectiveness>, Vec<MergeConflict>), TrackerError> {
+        for checked_type in [type_name, merged_type_name] {
+            if !self.type_list.contains(checked_type) {
+                return Err(TrackerError::UnknownType(checked_type.clone()));
+            }
+        }
+        let mut matchups = BTreeMap::new();
+        let mut conflicts = Vec::new();
+        if type_name == merged_type_name {
+            return Ok((matchups, conflicts));
+        }
+        let opposing_types = self.type_list.iter().filter(|current_type| *current_type != type_name && *current_type != merged_type_name);
+        for opposing_type in opposing_types {
+            let cells = [
+                ((type_name, opposing_type), (merged_type_name, opposing_type)),
+                ((opposing_type, type_name), (opposing_type, merged_type_name)),
+            ];
+            for ((attacking_type, defending_type), (merged_attacking_type, merged_defending_type)) in cells {
+                let kept = self.get_effectiveness(attacking_type, defending_type)?;
+                let merged = self.get_effectiveness(merged_attacking_type, merged_defending_type)?;
+                let cell = (attacking_type.clone(), defending_type.clone());
+                match combine_matchups(kept, merged) {
+                    None => {
+                        conflicts.push(MergeConflict { attacking_type: cell.0.clone(), defending_type: cell.1.clone(), kept, merged });
+                        matchups.insert(cell, kept);
+                    },
+                    Some(effectiveness) => {
+                        matchups.insert(cell, effectiveness);
+                    },
+                }
+            }
+        }
+        // The matchups of the two types against each other all become the matchup of the kept type against itself
+        let self_cells = [(type_name, merged_type_name), (merged_type_name, type_name), (merged_type_name, merged_type_name)];
+        let mut kept = self.get_effectiveness(type_name, type_name)?;
+        let mut conflict = None;
+        for (attacking_type, defending_type) in self_cells {
+            let merged = self.get_effectiveness(attacking_type, defending_type)?;
+            match combine_matchups(kept, merged) {
+                None => conflict = conflict.or(Some(merged)),
+                Some(effectiveness) => kept = effectiveness,
+            }
+        }
+        if let Some(merged) = conflict {
+            conflicts.push(MergeConflict { attacking_type: type_name.clone(), defending_type: type_name.clone(), kept, merged });
+        }
+        matchups.insert((type_name.clone(), type_name.clone()), kept);
+        return Ok((matchups, conflicts));
+    }
+
+    /// Lists the matchups `type_name` and `merged_type_name` disagree on, to resolve them before merging
+    pub fn get_merge_conflicts(&self, type_name: &String, merged_type_name: &String) -> Result<Vec<MergeConflict>, TrackerError> {
+        let (_, conflicts) = self.merged_matchups(type_name, merged_type_name)?;
+        return Ok(conflicts);
+    }
+
+    /// Folds `merged_type_name` into `type_name` and removes it, for types that were entered twice under different names
+    ///
+    /// Matchups only one of the types knows are kept, and `resolutions` gives the effectiveness to use for conflicts
+    /// from [`TypeChart::get_merge_conflicts`], the matchup of `type_name` is kept for conflicts without a resolution
+    pub fn merge_types(&mut self, type_name: &String, merged_type_name: &String, resolutions: &[(MergeConflict, Effectiveness)]) -> Result<(), TrackerError> {
+        let (mut matchups, _) = self.merged_matchups(type_name, merged_type_name)?;
+        if type_name == merged_type_name {
+            return Ok(());
+        }
+        for (conflict, effectiveness) in resolutions {
+            let cell = (conflict.attacking_type.clone(), conflict.defending_type.clone());
+            if matchups.contains_key(&cell) {
+                matchups.insert(cell, *effectiveness);
+            }
+        }
+        let mut edits = Vec::new();
+        for ((attacking_type, defending_type), effectiveness) in matchups {
+            let previous = self.get_effectiveness(&attacking_type, &defending_type)?;
+            if previous != effectiveness {
+                edits.push(Edit::SetEffectiveness { attacking_type, defending_type, previous, new: effectiveness });
+            }
+        }
+        // Species and observations of the merged type get the type it is merged into
+        edits.extend(self.species_type_edits(merged_type_name, Some(type_name)));
+        edits.extend(self.observation_type_edits(merged_type_name, Some(type_name)));
+        edits.push(self.removal_edit(merged_type_name, Vec::new())?);
+        for edit in &edits {
+            if let Edit::SetEffectiveness { attacking_type, defending_type, previous, new } = edit {
+                self.record_discovery(attacking_type, defending_type, *previous, *new, Some(format!("Merged from {}", merged_type_name)));
+            }
+        }
+        self.commit_edit(Edit::MergeTypes { type_name: type_name.clone(), merged_type_name: merged_type_name.clone(), edits });
+        return Ok(());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::type_chart_with, type_chart::Multiplier};
+
+    fn set(type_chart: &mut TypeChart, attacking_type: &str, defending_type: &str, effectiveness: Effectiveness) {
+        type_chart.add_effectiveness(&attacking_type.to_string(), &defending_type.to_string(), effectiveness).unwrap();
+    }
+
+    fn effectiveness(type_chart: &TypeChart, attacking_type: &str, defending_type: &str) -> Effectiveness {
+        return type_chart.get_effectiveness(&attacking_type.to_string(), &defending_type.to_string()).unwrap();
+    }
+
+    #[test]
+    fn conflicting_matchups_are_resolved_or_keep_the_kept_type() {
+        let mut type_chart = type_chart_with(&["Fire", "Fier", "Grass", "Water"]);
+        set(&mut type_chart, "Fire", "Grass", Effectiveness::SUPER_EFFECTIVE);
+        set(&mut type_chart, "Fier", "Grass", Effectiveness::NOT_VERY_EFFECTIVE);
+        set(&mut type_chart, "Water", "Fire", Effectiveness::SUPER_EFFECTIVE);
+        set(&mut type_chart, "Water", "Fier", Effectiveness::NEUTRAL);
+        // Only known by the merged type, or suspected by one and confirmed by the other
+        set(&mut type_chart, "Grass", "Fier", Effectiveness::NOT_VERY_EFFECTIVE);
+        set(&mut type_chart, "Fire", "Water", Effectiveness::Suspected(Multiplier::NEUTRAL));
+        set(&mut type_chart, "Fier", "Water", Effectiveness::NOT_VERY_EFFECTIVE);
+
+        let conflicts = type_chart.get_merge_conflicts(&"Fire".to_string(), &"Fier".to_string()).unwrap();
+        assert_eq!(conflicts, vec![
+            MergeConflict { attacking_type: "Fire".to_string(), defending_type: "Grass".to_string(), kept: Effectiveness::SUPER_EFFECTIVE, merged: Effectiveness::NOT_VERY_EFFECTIVE },
+            MergeConflict { attacking_type: "Water".to_string(), defending_type: "Fire".to_string(), kept: Effectiveness::SUPER_EFFECTIVE, merged: Effectiveness::NEUTRAL },
+        ]);
+        let resolutions = [(conflicts[0].clone(), Effectiveness::NOT_VERY_EFFECTIVE)];
+        type_chart.merge_types(&"Fire".to_string(), &"Fier".to_string(), &resolutions).unwrap();
+
+        assert_eq!(type_chart.get_type_list(), ["Fire", "Grass", "Water"].map(String::from));
+        assert_eq!(effectiveness(&type_chart, "Fire", "Grass"), Effectiveness::NOT_VERY_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Water", "Fire"), Effectiveness::SUPER_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Grass", "Fire"), Effectiveness::NOT_VERY_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Fire", "Water"), Effectiveness::NOT_VERY_EFFECTIVE);
+
+        type_chart.undo();
+        assert_eq!(type_chart.get_type_list(), ["Fire", "Fier", "Grass", "Water"].map(String::from));
+        assert_eq!(effectiveness(&type_chart, "Fire", "Grass"), Effectiveness::SUPER_EFFECTIVE);
+        assert_eq!(effectiveness(&type_chart, "Fier", "Grass"), Effectiveness::NOT_VERY_EFFECTIVE);
+    }
+
+    #[test]
+    fn matchups_against_each_other_give_a_single_self_matchup() {
+        let mut type_chart = type_chart_with(&["Fire", "Fier"]);
+        set(&mut type_chart, "Fier", "Fire", Effectiveness::NOT_VERY_EFFECTIVE);
+        set(&mut type_chart, "Fier", "Fier", Effectiveness::NOT_VERY_EFFECTIVE);
+        type_chart.merge_types(&"Fire".to_string(), &"Fier".to_string(), &[]).unwrap();
+        assert_eq!(effectiveness(&type_chart, "Fire", "Fire"), Effectiveness::NOT_VERY_EFFECTIVE);
+
+        let mut type_chart = type_chart_with(&["Fire", "Fier"]);
+        set(&mut type_chart, "Fire", "Fire", Effectiveness::NOT_VERY_EFFECTIVE);
+        set(&mut type_chart, "Fire", "Fier", Effectiveness::SUPER_EFFECTIVE);
+        set(&mut type_chart, "Fier", "Fire", Effectiveness::NEUTRAL);
+        set(&mut type_chart, "Fier", "Fier", Effectiveness::NOT_VERY_EFFECTIVE);
+        let conflicts = type_chart.get_merge_conflicts(&"Fire".to_string(), &"Fier".to_string()).unwrap();
+        // Three of the four matchups disagree with the kept one, but they are a single conflict
+        assert_eq!(conflicts, vec![
+            MergeConflict { attacking_type: "Fire".to_string(), defending_type: "Fire".to_string(), kept: Effectiveness::NOT_VERY_EFFECTIVE, merged: Effectiveness::SUPER_EFFECTIVE },
+        ]);
+        type_chart.merge_types(&"Fire".to_string(), &"Fier".to_string(), &[(conflicts[0].clone(), Effectiveness::IMMUNE)]).unwrap();
+        assert_eq!(effectiveness(&type_chart, "Fire", "Fire"), Effectiveness::IMMUNE);
+    }
+}
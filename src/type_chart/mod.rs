@@ -1,2 +1,16 @@
+mod coverage;
+mod discovery;
+mod effectiveness;
+mod inference;
+mod journal;
+mod merge;
+mod species;
 mod type_chart;
-pub use type_chart::{TypeChart, TypeMap, effectiveness_f32_to_string, effectiveness_string_to_f32, ALL_POSSIBLE_EFFECTIVENESSES};
+mod type_names;
+pub use coverage::Coverage;
+pub use discovery::Discovery;
+pub use effectiveness::{CombinedEffectiveness, Effectiveness, Multiplier};
+pub use inference::{BattleMessage, Contradiction, Deduction, Inference, Observation};
+pub use journal::{Edit, JournalEntry, MAX_HISTORY_LENGTH};
+pub use merge::MergeConflict;
+pub use type_chart::{EffectivenessMap, TypeChart, TypeMap};
@@ -0,0 +1,169 @@
+use std::collections::BTreeMap;
+
+use crate::{error::TrackerError, type_chart::{Edit, TypeChart}};
+
+impl TypeChart {
+    /// Returns every species with its types
+    pub fn get_species(&self) -> BTreeMap<String, Vec<String>> {
+        return self.species.clone();
+    }
+
+    // The case is ignored, Pokemon names are written in many ways
+    fn find_species(&self, species_name: &str) -> Option<&String> {
+        let lowercase_name = species_name.trim().to_lowercase();
+        return self.species.keys().find(|current_species| current_species.to_lowercase() == lowercase_name);
+    }
+
+    /// Returns the types of a species, the case of its name is ignored
+    pub fn get_species_types(&self, species_name: &str) -> Result<Vec<String>, TrackerError> {
+        match self.find_species(species_name) {
+            None => return Err(TrackerError::UnknownSpecies(species_name.trim().to_string())),
+            Some(found_species) => return Ok(self.species[found_species].clone()),
+        }
+    }
+
+    /// Records the types of a species, replacing the ones it had, without any type the species is removed
+    pub fn set_species(&mut self, species_name: &String, type_names: &[String]) -> Result<(), TrackerError> {
+        let mut new_types: Vec<String> = Vec::new();
+        for type_name in type_names {
+            if !self.type_list.contains(type_name) {
+                return Err(TrackerError::UnknownType(type_name.clone()));
+            }
+            if !new_types.contains(type_name) {
+                new_types.push(type_name.clone());
+            }
+        }
+        // Keeps the name the species was first written with
+        let species_name = self.find_species(species_name).cloned().unwrap_or_else(|| species_name.trim().to_string());
+        let previous = self.species.get(&species_name).cloned();
+        let new = if new_types.is_empty() { None } else { Some(new_types) };
+        if previous != new {
+            self.commit_edit(Edit::SetSpecies { species_name, previous, new });
+        }
+        return Ok(());
+    }
+
+    /// Removes a species from the species list
+    pub fn remove_species(&mut self, species_name: &str) -> Result<(), TrackerError> {
+        let species_name = match self.find_species(species_name) {
+            None => return Err(TrackerError::UnknownSpecies(species_name.trim().to_string())),
+            Some(found_species) => found_species.clone(),
+        };
+        let previous = self.species.get(&species_name).cloned();
+        self.commit_edit(Edit::SetSpecies { species_name, previous, new: None });
+        return Ok(());
+    }
+
+    /// Adds the types that are missing and sets the types of every species, as a single change that is undone at once
+    ///
+    /// `source` says where the types and species come from in the history, species without any type are left untouched
+    pub fn import_species(&mut self, source: &String, type_names: &[String], species: &[(String, Vec<String>)]) -> Result<(), TrackerError> {
+        let mut edits = Vec::new();
+        let mut type_list = self.type_list.clone();
+        for type_name in type_names {
+            if !type_list.contains(type_name) {
+                type_list.push(type_name.clone());
+                edits.push(Edit::AddType { type_name: type_name.clone() });
+            }
+        }
+        // Species already imported are compared with what the previous edits made of them
+        let mut species_list = self.species.clone();
+        for (species_name, species_types) in species {
+            let mut new_types: Vec<String> = Vec::new();
+            for type_name in species_types {
+                if !type_list.contains(type_name) {
+                    return Err(TrackerError::UnknownType(type_name.clone()));
+                }
+                if !new_types.contains(type_name) {
+                    new_types.push(type_name.clone());
+                }
+            }
+            if new_types.is_empty() {
+                continue;
+            }
+            let lowercase_name = species_name.trim().to_lowercase();
+            let species_name = species_list.keys()
+                .find(|current_species| current_species.to_lowercase() == lowercase_name)
+                .cloned()
+                .unwrap_or_else(|| species_name.trim().to_string());
+            let previous = species_list.insert(species_name.clone(), new_types.clone());
+            if previous.as_ref() != Some(&new_types) {
+                edits.push(Edit::SetSpecies { species_name, previous, new: Some(new_types) });
+            }
+        }
+        if !edits.is_empty() {
+            self.commit_edit(Edit::ImportSpecies { source: source.clone(), edits });
+        }
+        return Ok(());
+    }
+
+    /// Finds the types of a defender described by the user, every name is either a species or a type
+    /// (see [`TypeChart::resolve_type_name`]), so "Mightyena" gives the types of Mightyena
+    pub fn resolve_defender_types(&self, names: &[String]) -> Result<Vec<String>, TrackerError> {
+        let mut type_names: Vec<String> = Vec::new();
+        for name in names {
+            let found_types = match self.get_species_types(name) {
+                Ok(species_types) => species_types,
+                Err(_) => vec![self.resolve_type_name(name)?],
+            };
+            for type_name in found_types {
+                if !type_names.contains(&type_name) {
+                    type_names.push(type_name);
+                }
+            }
+        }
+        return Ok(type_names);
+    }
+
+    // Edits giving the species with `type_name` `new_type_name` instead, or taking the type away from them without one,
+    // species left without any type are removed
+    pub(crate) fn species_type_edits(&self, type_name: &String, new_type_name: Option<&String>) -> Vec<Edit> {
+        let mut edits = Vec::new();
+        for (species_name, species_types) in self.species.iter().filter(|(_, species_types)| species_types.contains(type_name)) {
+            let mut new_types: Vec<String> = Vec::new();
+            for species_type in species_types {
+                let species_type = if species_type == type_name { new_type_name } else { Some(species_type) };
+                if let Some(species_type) = species_type && !new_types.contains(species_type) {
+                    new_types.push(species_type.clone());
+                }
+            }
+            let new = if new_types.is_empty() { None } else { Some(new_types) };
+            edits.push(Edit::SetSpecies { species_name: species_name.clone(), previous: Some(species_types.clone()), new });
+        }
+        return edits;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::type_chart_with;
+
+    fn type_chart_with_species() -> TypeChart {
+        let mut type_chart = type_chart_with(&["Water", "Grass"]);
+        type_chart.set_species(&"Treecko".to_string(), &["Grass".to_string()]).unwrap();
+        type_chart.set_species(&"Lotad".to_string(), &["Water".to_string(), "Grass".to_string()]).unwrap();
+        return type_chart;
+    }
+
+    #[test]
+    fn removing_a_type_takes_it_out_of_the_species() {
+        let mut type_chart = type_chart_with_species();
+        type_chart.remove_existing_type(&"Grass".to_string()).unwrap();
+        assert_eq!(type_chart.get_species_types("Lotad").unwrap(), vec!["Water".to_string()]);
+        // Treecko has no type left
+        assert!(type_chart.get_species_types("Treecko").is_err());
+
+        type_chart.undo();
+        assert_eq!(type_chart.get_species_types("Lotad").unwrap(), vec!["Water".to_string(), "Grass".to_string()]);
+        assert_eq!(type_chart.get_species_types("Treecko").unwrap(), vec!["Grass".to_string()]);
+    }
+
+    #[test]
+    fn merging_types_gives_the_species_the_kept_type() {
+        let mut type_chart = type_chart_with_species();
+        type_chart.merge_types(&"Water".to_string(), &"Grass".to_string(), &[]).unwrap();
+        assert_eq!(type_chart.get_species_types("Lotad").unwrap(), vec!["Water".to_string()]);
+        assert_eq!(type_chart.get_species_types("Treecko").unwrap(), vec!["Water".to_string()]);
+    }
+}
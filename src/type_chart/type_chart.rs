@@ -1,278 +1,337 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-pub type TypeMap = HashMap<String, HashMap<String, f32>>;
+use chrono::Local;
 
+use crate::{error::TrackerError, type_chart::{CombinedEffectiveness, Discovery, Edit, Effectiveness, JournalEntry, Observation}};
+
+/// Effectiveness of every attacking type (outer key) against every defending type (inner key)
+pub type TypeMap = HashMap<String, HashMap<String, Effectiveness>>;
+/// Opposing types grouped by how effective the matchup is, from least to most effective
+pub type EffectivenessMap = BTreeMap<CombinedEffectiveness, Vec<String>>;
+
+/// The type chart being built while playing, every pair of types starts as unknown
 #[derive(Debug)]
 pub struct TypeChart {
-    type_map: TypeMap,
-    type_list: Vec<String>,
+    pub(crate) type_map: TypeMap,
+    pub(crate) type_list: Vec<String>,
+    pub(crate) metadata: BTreeMap<String, String>,
+    pub(crate) history: Vec<JournalEntry>,
+    // Most recently undone change last
+    pub(crate) undone_history: Vec<JournalEntry>,
+    pub(crate) session: String,
+    // Matchups learned since the type chart was loaded, not written in the discovery log yet
+    pub(crate) discoveries: Vec<Discovery>,
+    // What the game said about moves hitting Pokemon, most of them with several types
+    pub(crate) observations: Vec<Observation>,
+    // Types of every species met so far, saved next to the chart file
+    pub(crate) species: BTreeMap<String, Vec<String>>,
 }
 
-pub static ALL_POSSIBLE_EFFECTIVENESSES: [&str; 16] = [
-    "Immune",
-    "Triple Not Very Effective",
-    "Double Not Very Effective", "Double Not Very Effective?",
-    "Not Very Effective", "Not Very Effective?", "Not Very Effective??",
-    "Neutral", "Neutral?", "Neutral??",
-    "Super Effective", "Super Effective?", "Super Effective??",
-    "Double Super Effective", "Double Super Effective?",
-    "Triple Super Effective"
-];
-
 impl TypeChart {
+    /// Creates a type chart without any type
     pub fn empty() -> TypeChart {
-        return TypeChart { type_map: HashMap::new(), type_list: Vec::new() }
+        return TypeChart::new(HashMap::new(), Vec::new());
     }
     
-    pub fn new(type_map: TypeMap, type_list: Vec<String>) -> TypeChart {
-        return TypeChart { type_map, type_list };
+    /// Creates a type chart from an already filled map, `type_list` gives the order the types are displayed and saved in
+    /// and every matchup missing from the map is unknown
+    pub fn new(mut type_map: TypeMap, type_list: Vec<String>) -> TypeChart {
+        // Every matchup missing from the map is unknown
+        for type_name in &type_list {
+            let effectiveness_map = type_map.entry(type_name.clone()).or_default();
+            for opposing_type in &type_list {
+                effectiveness_map.entry(opposing_type.clone()).or_insert(Effectiveness::Unknown);
+            }
+        }
+        return TypeChart { type_map, type_list, metadata: BTreeMap::new(), history: Vec::new(), undone_history: Vec::new(),
+            session: Local::now().format("%Y-%m-%d %H:%M:%S").to_string(), discoveries: Vec::new(), observations: Vec::new(), species: BTreeMap::new() };
     }
 
+    /// Returns true if the type chart doesn't have any type
     pub fn is_empty(&self) -> bool {
         return self.type_list.is_empty() && self.type_map.is_empty();
     }
 
+    /// Returns every type in the order they were added
     pub fn get_type_list(&self) -> Vec<String> {
         return self.type_list.clone();
     }
 
+    /// Returns a copy of every matchup of the type chart
     pub fn get_type_map(&self) -> TypeMap {
         return self.type_map.clone();
     }
 
-    pub fn add_new_type(&mut self, type_name: &String) {
+    /// Returns the free-form information saved with the type chart (name of the run, game version...)
+    pub fn get_metadata(&self) -> BTreeMap<String, String> {
+        return self.metadata.clone();
+    }
+
+    /// Sets a free-form information saved with the type chart, an empty value removes it
+    pub fn set_metadata(&mut self, key: &String, value: &String) {
+        let previous = self.metadata.get(key).cloned();
+        let new = if value.is_empty() { None } else { Some(value.clone()) };
+        if previous != new {
+            self.commit_edit(Edit::SetMetadata { key: key.clone(), previous, new });
+        }
+    }
+
+    /// Returns how effective `type_name` attacks are against `opposing_type_name`
+    pub fn get_effectiveness(&self, type_name: &String, opposing_type_name: &String) -> Result<Effectiveness, TrackerError> {
+        let effectiveness_map = match self.type_map.get(type_name) {
+            None => return Err(TrackerError::UnknownType(type_name.clone())),
+            Some(effectiveness_map) => effectiveness_map,
+        };
+        match effectiveness_map.get(opposing_type_name) {
+            None => return Err(TrackerError::UnknownType(opposing_type_name.clone())),
+            Some(effectiveness) => return Ok(*effectiveness),
+        }
+    }
+
+    /// Adds a new type whose matchups against every other type are unknown
+    pub fn add_new_type(&mut self, type_name: &String) -> Result<(), TrackerError> {
         // Check id the type already is in the list
         if self.type_list.contains(type_name) {
-            eprintln!("Type is already in the type chart");
+            return Err(TrackerError::DuplicateType(type_name.clone()));
+        }
+        self.commit_edit(Edit::AddType { type_name: type_name.clone() });
+        return Ok(());
+    }
+
+    /// Removes a type and every matchup involving it, the type is taken out of the species that have it
+    /// and the battle observations involving it are forgotten
+    pub fn remove_existing_type(&mut self, type_name: &String) -> Result<(), TrackerError> {
+        let mut edits = self.species_type_edits(type_name, None);
+        edits.extend(self.observation_type_edits(type_name, None));
+        let edit = self.removal_edit(type_name, edits)?;
+        self.commit_edit(edit);
+        return Ok(());
+    }
+
+    // `edits` are made before removing the type, merging types updates the species and observations itself
+    pub(crate) fn removal_edit(&self, type_name: &String, edits: Vec<Edit>) -> Result<Edit, TrackerError> {
+        let position = match self.type_list.iter().position(|current_type| current_type == type_name) {
+            None => return Err(TrackerError::UnknownType(type_name.clone())),
+            Some(position) => position,
+        };
+        // Known matchups are kept in the history to be able to put the type back
+        let attacking = self.type_map.get(type_name).into_iter().flatten()
+            .filter(|(_, effectiveness)| **effectiveness != Effectiveness::Unknown)
+            .map(|(defending_type, effectiveness)| (defending_type.clone(), *effectiveness))
+            .collect();
+        let defending = self.type_map.iter()
+            .filter_map(|(attacking_type, effectiveness_map)| effectiveness_map.get(type_name).map(|effectiveness| (attacking_type, effectiveness)))
+            .filter(|(attacking_type, effectiveness)| *attacking_type != type_name && **effectiveness != Effectiveness::Unknown)
+            .map(|(attacking_type, effectiveness)| (attacking_type.clone(), *effectiveness))
+            .collect();
+        return Ok(Edit::RemoveType { type_name: type_name.clone(), position, attacking, defending, edits });
+    }
+
+    /// Renames a type, keeping every matchup involving it
+    pub fn rename_type(&mut self, type_name: &String, new_type_name: &String) -> Result<(), TrackerError> {
+        if !self.type_list.contains(type_name) {
+            return Err(TrackerError::UnknownType(type_name.clone()));
+        }
+        if self.type_list.contains(new_type_name) {
+            return Err(TrackerError::DuplicateType(new_type_name.clone()));
+        }
+        self.commit_edit(Edit::RenameType { previous: type_name.clone(), new: new_type_name.clone() });
+        self.record_renamed_type(new_type_name, type_name);
+        return Ok(());
+    }
+
+    /// Sets how effective `type_name` attacks are against `opposing_type_name`
+    pub fn add_effectiveness(&mut self, type_name: &String, opposing_type_name: &String, effectiveness: Effectiveness) -> Result<(), TrackerError> {
+        return self.add_effectiveness_with_note(type_name, opposing_type_name, effectiveness, None);
+    }
+
+    /// Sets how effective `type_name` attacks are against `opposing_type_name`, the change is added to the discovery log
+    /// with `note` saying where or how it was learned
+    pub fn add_effectiveness_with_note(&mut self, type_name: &String, opposing_type_name: &String, effectiveness: Effectiveness, note: Option<String>) -> Result<(), TrackerError> {
+        let previous = self.get_effectiveness(type_name, opposing_type_name)?;
+        if previous != effectiveness {
+            self.commit_edit(Edit::SetEffectiveness {
+                attacking_type: type_name.clone(),
+                defending_type: opposing_type_name.clone(),
+                previous,
+                new: effectiveness,
+            });
+            self.record_discovery(type_name, opposing_type_name, previous, effectiveness, note);
+        }
+        return Ok(());
+    }
+
+    // Changes below are not recorded in the history, they are only used to make or revert recorded changes
+
+    pub(crate) fn insert_type(&mut self, type_name: &String, position: usize) {
+        if self.type_list.contains(type_name) {
             return;
         }
-        self.type_list.push(type_name.clone());
+        self.type_list.insert(position.min(self.type_list.len()), type_name.clone());
         for current_effectiveness_map in self.type_map.values_mut() {
-            current_effectiveness_map.insert(type_name.clone(), -1.);
+            current_effectiveness_map.insert(type_name.clone(), Effectiveness::Unknown);
         }
         let mut new_effectiveness_map = HashMap::new();
         for current_type in &self.type_list {
-            new_effectiveness_map.insert(current_type.clone(), -1.);
+            new_effectiveness_map.insert(current_type.clone(), Effectiveness::Unknown);
         }
         self.type_map.insert(type_name.clone(), new_effectiveness_map);
-        println!("New type {} added", type_name);
     }
 
-    pub fn remove_existing_type(&mut self, type_name: &String) {
-        let idx = match self.type_list.iter().position(|current_type| current_type == type_name) {
-            None => {
-                eprintln!("There is no type named {}", type_name);
-                return;
-            },
-            Some(idx) => idx,
-        };
-        self.type_list.remove(idx);
-        
+    pub(crate) fn delete_type(&mut self, type_name: &String) {
+        self.type_list.retain(|current_type| current_type != type_name);
         for current_effectiveness_map in self.type_map.values_mut() {
             current_effectiveness_map.remove(type_name);
         }
         self.type_map.remove(type_name);
-        println!("Removed type {}", type_name);
     }
 
-    pub fn add_effectiveness(&mut self, type_name: &String, opposing_type_name: &String, effectiveness: String) {
-        let effectiveness_value = effectiveness_string_to_f32(&effectiveness).expect("Effectiveness doesn't exist");
-        let effectiveness_map = match self.type_map.get_mut(type_name) {
-            None => {
-                eprintln!("Type {} doesn't exist!", type_name);
-                return;
-            }
-            Some(effectiveness_map) => effectiveness_map,
-        };
-        // Check if the opposing type exists
-        if !self.type_list.contains(opposing_type_name) {
-            eprintln!("Type {} doesn't exist!", opposing_type_name);
+    pub(crate) fn replace_type_name(&mut self, type_name: &String, new_type_name: &String) {
+        if !self.type_list.contains(type_name) || self.type_list.contains(new_type_name) {
             return;
         }
-        effectiveness_map.insert(opposing_type_name.clone(), effectiveness_value);
-        println!("{} type attacks are now {} against {}", type_name, &effectiveness, opposing_type_name);
+        for current_type in self.type_list.iter_mut().filter(|current_type| *current_type == type_name) {
+            *current_type = new_type_name.clone();
+        }
+        for current_effectiveness_map in self.type_map.values_mut() {
+            if let Some(effectiveness) = current_effectiveness_map.remove(type_name) {
+                current_effectiveness_map.insert(new_type_name.clone(), effectiveness);
+            }
+        }
+        if let Some(effectiveness_map) = self.type_map.remove(type_name) {
+            self.type_map.insert(new_type_name.clone(), effectiveness_map);
+        }
+        for species_types in self.species.values_mut() {
+            for current_type in species_types.iter_mut().filter(|current_type| *current_type == type_name) {
+                *current_type = new_type_name.clone();
+            }
+        }
+        for observation in &mut self.observations {
+            let observed_types = std::iter::once(&mut observation.attacking_type).chain(observation.defending_types.iter_mut());
+            for current_type in observed_types.filter(|current_type| *current_type == type_name) {
+                *current_type = new_type_name.clone();
+            }
+        }
+    }
+
+    pub(crate) fn set_matchup(&mut self, type_name: &String, opposing_type_name: &String, effectiveness: Effectiveness) {
+        if let Some(current_effectiveness) = self.type_map.get_mut(type_name).and_then(|effectiveness_map| effectiveness_map.get_mut(opposing_type_name)) {
+            *current_effectiveness = effectiveness;
+        }
+    }
+
+    // Replaces the observation at `position` by `new`, `previous` tells whether there is one to replace
+    pub(crate) fn set_observation_value(&mut self, position: usize, previous: &Option<Observation>, new: &Option<Observation>) {
+        match (previous, new) {
+            (Some(_), None) if position < self.observations.len() => {
+                self.observations.remove(position);
+            },
+            (None, Some(observation)) => self.observations.insert(position.min(self.observations.len()), observation.clone()),
+            (Some(_), Some(observation)) if position < self.observations.len() => self.observations[position] = observation.clone(),
+            _ => (),
+        }
+    }
+
+    pub(crate) fn set_species_value(&mut self, species_name: &String, type_names: &Option<Vec<String>>) {
+        match type_names {
+            None => self.species.remove(species_name),
+            Some(type_names) => self.species.insert(species_name.clone(), type_names.clone()),
+        };
+    }
+
+    pub(crate) fn set_metadata_value(&mut self, key: &String, value: &Option<String>) {
+        match value {
+            None => self.metadata.remove(key),
+            Some(value) => self.metadata.insert(key.clone(), value.clone()),
+        };
     }
 
-    pub fn get_attacking_effectiveness(&mut self, type_name: &String) -> Result<HashMap<String, Vec<String>>, ()> {
+    /// Groups every type by how effective `type_name` attacks are against it, unknown matchups are left out
+    /// and suspected ones are reported in [`CombinedEffectiveness::suspected_count`]
+    pub fn get_attacking_effectiveness(&self, type_name: &String) -> Result<EffectivenessMap, TrackerError> {
         let effectiveness_map = match self.type_map.get(type_name) {
-            None => {
-                eprintln!("Type {} doesn't exist!", type_name);
-                return Err(());
-            }
+            None => return Err(TrackerError::UnknownType(type_name.clone())),
             Some(effectiveness_map) => effectiveness_map,
         };
-        let mut reverse_effectiveness_map: HashMap<String, Vec<String>> = HashMap::new();
-        for effectiveness in vec!["Immune", "Not Very Effective", "Neutral", "Super Effective"] {
-            reverse_effectiveness_map.insert(effectiveness.to_string(), Vec::new());
-        }
-        for (opposing_type, effectiveness) in effectiveness_map {
-            if effectiveness == &-1. {
-                continue;
-            }
-            let effectiveness_string = effectiveness_f32_to_string(*effectiveness, 0).expect("Effectiveness doesn't exist").to_string();
-            let type_list = match reverse_effectiveness_map.get_mut(&effectiveness_string) {
-                None => {
-                    // Should not happen, unless we read a file with wrong effectivenesses
-                    eprintln!("Effectiveness {} doesn't exist", effectiveness);
-                    return Err(());
-                },
-                Some(type_list) => type_list,
+        let mut reverse_effectiveness_map: EffectivenessMap = BTreeMap::new();
+        for opposing_type in &self.type_list {
+            let effectiveness = match effectiveness_map.get(opposing_type) {
+                None | Some(Effectiveness::Unknown) => continue,
+                Some(effectiveness) => effectiveness,
             };
-            type_list.push(opposing_type.clone());
+            reverse_effectiveness_map.entry(CombinedEffectiveness::combine([effectiveness])).or_default().push(opposing_type.clone());
         }
         return Ok(reverse_effectiveness_map);
     }
 
-    pub fn get_defensive_effectiveness(&mut self, type_name: &String) -> Result<HashMap<String, Vec<String>>, ()> {
+    /// Groups every type by how effective its attacks are against `type_name`, unknown matchups are left out
+    /// and suspected ones are reported in [`CombinedEffectiveness::suspected_count`]
+    pub fn get_defensive_effectiveness(&self, type_name: &String) -> Result<EffectivenessMap, TrackerError> {
         if !self.type_list.contains(type_name) {
-            eprintln!("Type {} isn't in the type chart", type_name);
-            return Err(());
+            return Err(TrackerError::UnknownType(type_name.clone()));
         }
-        let mut reverse_effectiveness_map: HashMap<String, Vec<String>> = HashMap::new();
-        for effectiveness in vec!["Immune", "Not Very Effective", "Neutral", "Super Effective"] {
-            reverse_effectiveness_map.insert(effectiveness.to_string(), Vec::new());
-        }
-        for (opposing_type, effectiveness_map) in &self.type_map {
-            let effectiveness = match effectiveness_map.get(type_name) {
-                None => {
-                    // Should not happen, since we checked before that the type exists
-                    eprintln!("Type {} doesn't have an effectiveness with {}", type_name, opposing_type);
-                    continue;
-                },
+        let mut reverse_effectiveness_map: EffectivenessMap = BTreeMap::new();
+        for opposing_type in &self.type_list {
+            // A missing effectiveness should not happen, since we checked before that the type exists
+            let effectiveness = match self.type_map.get(opposing_type).and_then(|effectiveness_map| effectiveness_map.get(type_name)) {
+                None | Some(Effectiveness::Unknown) => continue,
                 Some(effectiveness) => effectiveness,
             };
-            if effectiveness == &-1. {
-                continue;
-            }
-            let effectiveness_string = effectiveness_f32_to_string(*effectiveness, 0).expect("Effectiveness doesn't exist").to_string();
-            let type_list = match reverse_effectiveness_map.get_mut(&effectiveness_string) {
-                None => {
-                    // Should not happen, unless we read a file with wrong effectivenesses
-                    eprintln!("Effectiveness {} doesn't exist", effectiveness);
-                    return Err(());
-                },
-                Some(type_list) => type_list,
-            };
-            type_list.push(opposing_type.clone());
+            reverse_effectiveness_map.entry(CombinedEffectiveness::combine([effectiveness])).or_default().push(opposing_type.clone());
         }
         return Ok(reverse_effectiveness_map);
     }
-    pub fn get_multiple_defensive_effectiveness(&mut self, first_type_name: &String, second_type_name: Option<&String>, third_type_name: Option<&String>) -> Result<HashMap<String, Vec<String>>, ()> {
+
+    /// Groups every type by how effective its attacks are against a Pokemon with all of `type_names`
+    ///
+    /// Unknown matchups count as neutral and are reported in [`CombinedEffectiveness::unknown_count`],
+    /// types whose matchups are all unknown are left out, and a type given several times only counts once
+    pub fn get_multiple_defensive_effectiveness(&self, type_names: &[String]) -> Result<EffectivenessMap, TrackerError> {
         // First check that all types are in the type list
-        if !self.type_list.contains(first_type_name) {
-            eprintln!("Type {} isn't in the type chart", first_type_name);
-            return Err(());
-        }
-        let mut nb_types = 1;
-        if let Some(second_type_name) = second_type_name {
-            nb_types += 1;
-            if !self.type_list.contains(second_type_name) {
-                eprintln!("Type {} isn't in the type chart", second_type_name);
-                return Err(());
+        let mut defending_types: Vec<&String> = Vec::new();
+        for type_name in type_names {
+            if !self.type_list.contains(type_name) {
+                return Err(TrackerError::UnknownType(type_name.clone()));
             }
-        }
-        if let Some(third_type_name) = third_type_name {
-            nb_types += 1;
-            if !self.type_list.contains(third_type_name) {
-                eprintln!("Type {} isn't in the type chart", third_type_name);
-                return Err(());
+            if !defending_types.contains(&type_name) {
+                defending_types.push(type_name);
             }
         }
-        let mut reverse_effectiveness_map: HashMap<String, Vec<String>> = HashMap::new();
-        for effectiveness in ALL_POSSIBLE_EFFECTIVENESSES {
-            reverse_effectiveness_map.insert(effectiveness.to_string(), Vec::new());
-        }
-        for (opposing_type, effectiveness_map) in &self.type_map {
-            let mut combined_effectiveness = match effectiveness_map.get(first_type_name) {
-                None => {
-                    // Should not happen, since we checked before that the type exists
-                    eprintln!("Type {} doesn't have an effectiveness with {}", first_type_name, opposing_type);
-                    continue;
-                },
-                Some(effectiveness) => *effectiveness,
+        let mut reverse_effectiveness_map: EffectivenessMap = BTreeMap::new();
+        for opposing_type in &self.type_list {
+            let effectiveness_map = match self.type_map.get(opposing_type) {
+                // Should not happen, the type list and the map are always in sync
+                None => continue,
+                Some(effectiveness_map) => effectiveness_map,
             };
-            let mut unknown_effectiveness_counter = 0;
-            if combined_effectiveness == -1. {
-                unknown_effectiveness_counter += 1;
-                combined_effectiveness = 1.;
-            }
-            if let Some(second_type_name) = second_type_name {
-                let second_effectiveness = match effectiveness_map.get(second_type_name) {
-                    None => {
-                        // Should not happen, since we checked before that the type exists
-                        eprintln!("Type {} doesn't have an effectiveness with {}", second_type_name, opposing_type);
-                        continue;
-                    },
-                    Some(effectiveness) => *effectiveness,
-                };
-                if second_effectiveness == -1. {
-                    unknown_effectiveness_counter += 1;
-                } else {
-                    combined_effectiveness *= second_effectiveness;
-                }
-            }
-            if let Some(third_type_name) = third_type_name {
-                let third_effectiveness = match effectiveness_map.get(third_type_name) {
-                    None => {
-                        // Should not happen, since we checked before that the type exists
-                        eprintln!("Type {} doesn't have an effectiveness with {}", third_type_name, opposing_type);
-                        continue;
-                    },
-                    Some(effectiveness) => *effectiveness,
-                };
-                if third_effectiveness == -1. {
-                    unknown_effectiveness_counter += 1;
-                } else {
-                    combined_effectiveness *= third_effectiveness;
-                }
-            }
-            if nb_types == unknown_effectiveness_counter {
+            // Since we checked before that the types exist, none of the effectivenesses should be missing
+            let effectivenesses: Vec<Effectiveness> = defending_types.iter()
+                .filter_map(|type_name| effectiveness_map.get(*type_name).copied())
+                .collect();
+            if effectivenesses.iter().all(|effectiveness| effectiveness.multiplier().is_none()) {
                 // We don't know anything about this type
                 continue;
             }
-            let effectiveness_string = effectiveness_f32_to_string(combined_effectiveness, unknown_effectiveness_counter).expect("Effectiveness doesn't exist");
-            match reverse_effectiveness_map.get_mut(&effectiveness_string) {
-                None => {
-                    // Should not happen, unless we read a file with wrong effectivenesses
-                    eprintln!("Effectiveness {} doesn't exist", combined_effectiveness);
-                    return Err(());
-                },
-                Some(type_list) => type_list.push(opposing_type.clone()),
-            }
+            let combined_effectiveness = CombinedEffectiveness::combine(&effectivenesses);
+            reverse_effectiveness_map.entry(combined_effectiveness).or_default().push(opposing_type.clone());
         }
         
         return Ok(reverse_effectiveness_map);
     }
 }
 
-pub fn effectiveness_string_to_f32(effectiveness: &String) -> Result<f32, ()> {
-    match effectiveness.as_str() {
-        "Immune" => Ok(0.),
-        "Triple Not Very Effective" => Ok(0.125),
-        "Double Not Very Effective" => Ok(0.25),
-        "Not Very Effective" => Ok(0.5),
-        "Neutral" => Ok(1.),
-        "Super Effective" => Ok(2.),
-        "Double Super Effective" => Ok(4.),
-        "Tripel Super Effective" => Ok(8.),
-        "?" => Ok(-1.), // Not sure how to represent this yet
-        _ => Err(())
-    }
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{test_helpers::type_chart_with, type_chart::Multiplier};
 
-pub fn effectiveness_f32_to_string(effectiveness: f32, unknown_effectiveness_counter: usize) -> Result<String, ()> {
-    let mut effectiveness_string = match effectiveness {
-        0. => return Ok("Immune".to_string()),
-        0.125 => Ok("Triple Not Very Effective"),
-        0.25 => Ok("Double Not Very Effective"),
-        0.5 => Ok("Not Very Effective"),
-        1. => Ok("Neutral"),
-        2. => Ok("Super Effective"),
-        4. => Ok("Double Super Effective"),
-        8. => Ok("Triple Super Effective"),
-        -1. => Ok("?"),
-        _ => Err(()),
-    }?.to_string();
-    effectiveness_string.push_str(&"?".repeat(unknown_effectiveness_counter));
-    return Ok(effectiveness_string);
+    #[test]
+    fn a_defending_type_given_twice_counts_once() {
+        let mut type_chart = type_chart_with(&["Water", "Grass"]);
+        type_chart.add_effectiveness(&"Grass".to_string(), &"Water".to_string(), Effectiveness::SUPER_EFFECTIVE).unwrap();
+        let twice = type_chart.get_multiple_defensive_effectiveness(&["Water".to_string(), "Water".to_string()]).unwrap();
+        assert_eq!(twice, type_chart.get_multiple_defensive_effectiveness(&["Water".to_string()]).unwrap());
+        let combined_effectiveness = twice.keys().next().unwrap();
+        assert_eq!((combined_effectiveness.multiplier, combined_effectiveness.unknown_count), (Multiplier::SUPER_EFFECTIVE, 0));
+    }
 }
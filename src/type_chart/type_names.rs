@@ -0,0 +1,118 @@
+use crate::{error::TrackerError, type_chart::TypeChart};
+
+const MAX_SUGGESTIONS: usize = 3;
+
+// Number of single character insertions, deletions, substitutions or swaps of two neighbouring characters
+// to go from one name to the other
+fn edit_distance(first: &str, second: &str) -> usize {
+    let first: Vec<char> = first.chars().collect();
+    let second: Vec<char> = second.chars().collect();
+    let mut distances = vec![vec![0; second.len() + 1]; first.len() + 1];
+    for (first_index, row) in distances.iter_mut().enumerate() {
+        row[0] = first_index;
+    }
+    distances[0] = (0..=second.len()).collect();
+    for first_index in 1..=first.len() {
+        for second_index in 1..=second.len() {
+            let substitution_cost = if first[first_index - 1] == second[second_index - 1] { 0 } else { 1 };
+            let mut distance = (distances[first_index - 1][second_index - 1] + substitution_cost)
+                .min(distances[first_index - 1][second_index] + 1)
+                .min(distances[first_index][second_index - 1] + 1);
+            let is_swap = first_index > 1 && second_index > 1
+                && first[first_index - 1] == second[second_index - 2]
+                && first[first_index - 2] == second[second_index - 1];
+            if is_swap {
+                distance = distance.min(distances[first_index - 2][second_index - 2] + 1);
+            }
+            distances[first_index][second_index] = distance;
+        }
+    }
+    return distances[first.len()][second.len()];
+}
+
+impl TypeChart {
+    /// Finds the type a name typed by the user refers to
+    ///
+    /// The case is ignored, and the start of a name is enough as long as only one type starts with it.
+    /// When no type matches, the error suggests the types with the closest names
+    pub fn resolve_type_name(&self, type_name: &str) -> Result<String, TrackerError> {
+        let type_name = type_name.trim();
+        if let Some(found_type) = self.type_list.iter().find(|current_type| *current_type == type_name) {
+            return Ok(found_type.clone());
+        }
+        let lowercase_name = type_name.to_lowercase();
+        let same_names: Vec<String> = self.type_list.iter()
+            .filter(|current_type| current_type.to_lowercase() == lowercase_name)
+            .cloned()
+            .collect();
+        match same_names.len() {
+            0 => (),
+            1 => return Ok(same_names[0].clone()),
+            _ => return Err(TrackerError::AmbiguousType { type_name: type_name.to_string(), candidates: same_names }),
+        }
+        if !lowercase_name.is_empty() {
+            let prefixed_names: Vec<String> = self.type_list.iter()
+                .filter(|current_type| current_type.to_lowercase().starts_with(&lowercase_name))
+                .cloned()
+                .collect();
+            match prefixed_names.len() {
+                0 => (),
+                1 => return Ok(prefixed_names[0].clone()),
+                _ => return Err(TrackerError::AmbiguousType { type_name: type_name.to_string(), candidates: prefixed_names }),
+            }
+        }
+        let suggestions = self.suggest_type_names(type_name);
+        if suggestions.is_empty() {
+            return Err(TrackerError::UnknownType(type_name.to_string()));
+        }
+        return Err(TrackerError::MisspelledType { type_name: type_name.to_string(), suggestions });
+    }
+
+    /// Returns the types whose names are close to `type_name`, from the closest one
+    pub fn suggest_type_names(&self, type_name: &str) -> Vec<String> {
+        let lowercase_name = type_name.trim().to_lowercase();
+        // Allows about one typo every three letters
+        let max_distance = (lowercase_name.chars().count() / 3).max(1);
+        let mut suggestions: Vec<(usize, &String)> = self.type_list.iter()
+            .map(|current_type| (edit_distance(&lowercase_name, &current_type.to_lowercase()), current_type))
+            .filter(|(distance, _)| *distance <= max_distance)
+            .collect();
+        suggestions.sort_by_key(|(distance, _)| *distance);
+        return suggestions.into_iter().take(MAX_SUGGESTIONS).map(|(_, current_type)| current_type.clone()).collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_helpers::type_chart_with;
+
+    #[test]
+    fn edit_distance_counts_swaps_as_a_single_typo() {
+        assert_eq!(edit_distance("fire", "fire"), 0);
+        assert_eq!(edit_distance("fier", "fire"), 1);
+        assert_eq!(edit_distance("fre", "fire"), 1);
+        assert_eq!(edit_distance("grass", "gras"), 1);
+        assert_eq!(edit_distance("water", "fire"), 4);
+        assert_eq!(edit_distance("", "ice"), 3);
+    }
+
+    #[test]
+    fn names_are_resolved_ignoring_case_and_by_their_start() {
+        let type_chart = type_chart_with(&["Fire", "Fighting", "Grass", "grass"]);
+        assert_eq!(type_chart.resolve_type_name(" fire ").unwrap(), "Fire");
+        assert_eq!(type_chart.resolve_type_name("FIG").unwrap(), "Fighting");
+        assert_eq!(type_chart.resolve_type_name("Grass").unwrap(), "Grass");
+        // Without an exact match the case can't tell Grass and grass apart
+        assert!(matches!(type_chart.resolve_type_name("GRASS"), Err(TrackerError::AmbiguousType { candidates, .. }) if candidates == ["Grass", "grass"]));
+        assert!(matches!(type_chart.resolve_type_name("f"), Err(TrackerError::AmbiguousType { candidates, .. }) if candidates == ["Fire", "Fighting"]));
+    }
+
+    #[test]
+    fn unknown_names_get_the_closest_types_as_suggestions() {
+        let type_chart = type_chart_with(&["Fire", "Fairy", "Water", "Ice"]);
+        assert!(matches!(type_chart.resolve_type_name("Firy"), Err(TrackerError::MisspelledType { suggestions, .. }) if suggestions == ["Fire", "Fairy"]));
+        assert_eq!(type_chart.suggest_type_names("Watr"), ["Water"]);
+        assert!(matches!(type_chart.resolve_type_name("Dragon"), Err(TrackerError::UnknownType(type_name)) if type_name == "Dragon"));
+    }
+}